@@ -0,0 +1,255 @@
+//! `dqh` - headless Discord Quest Helper
+//!
+//! Thin command-line front end over `quest-core`, for machines where the Tauri
+//! window is not available.
+
+use anyhow::Result;
+use clap::{Args, Parser, Subcommand};
+use quest_core::discord_api::DiscordApiClient;
+use quest_core::dispatch::{self, Prepared, RunOptions};
+use quest_core::endpoints::DiscordEndpoints;
+use quest_core::logging;
+use quest_core::models::Quest;
+use quest_core::quest_completer::QuestControl;
+use quest_core::quest_events::{ChannelEvents, QuestEvent};
+
+#[derive(Parser)]
+#[command(name = "dqh", version, about = "Headless Discord Quest Helper")]
+struct Cli {
+    /// Discord token
+    #[arg(long, env = "DISCORD_TOKEN", hide_env_values = true)]
+    token: String,
+
+    /// Log messages at this level or more severe go to stderr (error, warn, info, debug, trace)
+    #[arg(long, global = true, default_value = "warn")]
+    log_level: tracing::Level,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Show the account the token belongs to
+    Whoami,
+    /// List, accept and run quests
+    #[command(subcommand)]
+    Quests(QuestsCommand),
+    /// Look up detectable games
+    #[command(subcommand)]
+    Games(GamesCommand),
+}
+
+#[derive(Subcommand)]
+enum QuestsCommand {
+    /// List available quests
+    List,
+    /// Enroll in a quest
+    Accept { quest_id: String },
+    /// Run a quest until it completes (Ctrl-C stops it)
+    Run(RunArgs),
+}
+
+#[derive(Args)]
+struct RunArgs {
+    quest_id: String,
+    /// Video seconds advanced per real second
+    #[arg(long, default_value_t = 1.0)]
+    speed: f64,
+    /// Seconds between video progress updates
+    #[arg(long, default_value_t = 3)]
+    interval: u64,
+    /// Stream key for stream quests (random if omitted)
+    #[arg(long)]
+    stream_key: Option<String>,
+}
+
+#[derive(Subcommand)]
+enum GamesCommand {
+    /// Search detectable games by name
+    Search { name: String },
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let cli = Cli::parse();
+    logging::install(cli.log_level, None);
+
+    // Endpoints come from DQH_ENDPOINTS_FILE and the DQH_* env overrides
+    let endpoints = DiscordEndpoints::load(None)?;
+    let client = DiscordApiClient::new(cli.token, endpoints)?;
+
+    match cli.command {
+        Command::Whoami => whoami(&client).await,
+        Command::Quests(QuestsCommand::List) => list_quests(&client).await,
+        Command::Quests(QuestsCommand::Accept { quest_id }) => {
+            client.accept_quest(&quest_id).await?;
+            println!("Enrolled in quest {}", quest_id);
+            Ok(())
+        }
+        Command::Quests(QuestsCommand::Run(args)) => run_quest(&client, args).await,
+        Command::Games(GamesCommand::Search { name }) => search_games(&client, &name).await,
+    }
+}
+
+async fn whoami(client: &DiscordApiClient) -> Result<()> {
+    let user = client.get_current_user().await?;
+    println!("{} ({})", user.global_name.as_deref().unwrap_or(&user.username), user.username);
+    println!("id: {}", user.id);
+    Ok(())
+}
+
+fn quest_status(quest: &Quest) -> &'static str {
+    if quest.is_completed() {
+        "completed"
+    } else if quest.is_enrolled() {
+        "enrolled"
+    } else {
+        "available"
+    }
+}
+
+fn truncate(text: &str, width: usize) -> String {
+    if text.chars().count() <= width {
+        text.to_string()
+    } else {
+        let cut: String = text.chars().take(width.saturating_sub(1)).collect();
+        format!("{}…", cut)
+    }
+}
+
+async fn list_quests(client: &DiscordApiClient) -> Result<()> {
+    let quests = client.get_quests().await?;
+    if quests.is_empty() {
+        println!("No quests available");
+        return Ok(());
+    }
+
+    println!("{:<20} {:<36} {:<20} {:>8} {:>8}  STATUS", "ID", "NAME", "TASK", "TARGET", "PROGRESS");
+    for quest in &quests {
+        // One line per task; the quest columns are only filled on the first
+        for (i, (name, task)) in quest.tasks().iter().enumerate() {
+            let (id, quest_name, status) = if i == 0 {
+                (quest.id.as_str(), truncate(quest.name(), 36), quest_status(quest))
+            } else {
+                ("", String::new(), "")
+            };
+            println!(
+                "{:<20} {:<36} {:<20} {:>7}s {:>7.1}%  {}",
+                id,
+                quest_name,
+                truncate(name, 20),
+                task.target,
+                quest.task_percent(name),
+                status
+            );
+        }
+    }
+    Ok(())
+}
+
+async fn search_games(client: &DiscordApiClient, name: &str) -> Result<()> {
+    let needle = name.to_lowercase();
+    let games = client.fetch_detectable_games().await?;
+    let matches: Vec<_> = games
+        .iter()
+        .filter(|g| g.name.to_lowercase().contains(&needle))
+        .collect();
+
+    if matches.is_empty() {
+        println!("No detectable games match \"{}\"", name);
+        return Ok(());
+    }
+
+    for game in matches {
+        println!("{:<20} {}", game.id, game.name);
+        for exe in &game.executables {
+            println!("{:<20}   {} ({})", "", exe.name, exe.os);
+        }
+    }
+    Ok(())
+}
+
+async fn run_quest(client: &DiscordApiClient, args: RunArgs) -> Result<()> {
+    let options = RunOptions {
+        speed_multiplier: args.speed,
+        heartbeat_interval: args.interval,
+        stream_key: args.stream_key,
+    };
+    let job = match dispatch::prepare_quest(client, &args.quest_id, &options).await? {
+        Prepared::AlreadyCompleted => {
+            println!("Quest {} is already completed", args.quest_id);
+            return Ok(());
+        }
+        Prepared::Run(job) => job,
+    };
+
+    println!(
+        "Running quest {} ({}, {}s needed, {:.1}% done) - press Ctrl-C to stop",
+        job.quest_id,
+        job.task.kind(),
+        job.seconds_needed,
+        job.progress
+    );
+
+    let (events, mut event_rx) = ChannelEvents::new();
+    let (cancel_tx, cancel_rx) = tokio::sync::mpsc::channel(1);
+
+    // Ctrl-C maps onto the completer's cancel channel
+    tokio::spawn(async move {
+        if tokio::signal::ctrl_c().await.is_ok() {
+            println!("\nStopping quest...");
+            let _ = cancel_tx.send(QuestControl::Stop).await;
+        }
+    });
+
+    let printer = tokio::spawn(async move {
+        let mut failed = false;
+        while let Some(event) = event_rx.recv().await {
+            match event {
+                // Already announced above
+                QuestEvent::Started(_) => {}
+                QuestEvent::Progress(progress) => println!("Progress: {:.1}%", progress),
+                QuestEvent::Completed => println!("Quest completed"),
+                QuestEvent::Stopped(Some(progress)) => println!("Quest stopped, progress saved at {:.1}%", progress),
+                QuestEvent::Stopped(None) => println!("Quest stopped"),
+                QuestEvent::Paused => println!("Quest paused"),
+                QuestEvent::Resumed => println!("Quest resumed"),
+                QuestEvent::Verified(_) => println!("Completion confirmed by Discord"),
+                QuestEvent::SessionClosed { acknowledged: true } => println!("Game session closed"),
+                QuestEvent::SessionClosed { acknowledged: false } => {
+                    eprintln!("Discord did not acknowledge the end of the game session")
+                }
+                QuestEvent::VerificationFailed(report) => {
+                    eprintln!(
+                        "Discord has not recorded the completion (progress {:.1}%{})",
+                        report.progress.unwrap_or(0.0),
+                        report.error.map(|e| format!(", {}", e)).unwrap_or_default()
+                    );
+                    failed = true;
+                }
+                QuestEvent::Retrying(notice) => eprintln!(
+                    "Request failed ({}/{}), retrying in {:.0}s: {}",
+                    notice.attempt, notice.max_attempts, notice.delay_secs, notice.message
+                ),
+                QuestEvent::Error(message) => {
+                    eprintln!("Quest error: {}", message);
+                    failed = true;
+                }
+            }
+        }
+        failed
+    });
+
+    let result = dispatch::run_job(client, &job, &events, cancel_rx).await;
+
+    // Close the event channel so the printer drains and exits
+    drop(events);
+    let failed = printer.await.unwrap_or(false);
+
+    result?;
+    if failed {
+        anyhow::bail!("Quest {} failed", job.quest_id);
+    }
+    Ok(())
+}
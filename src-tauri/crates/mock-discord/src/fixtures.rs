@@ -0,0 +1,179 @@
+use serde_json::{json, Value};
+
+/// Fixed timestamp used for every `*_at` field the mock reports
+pub const MOCK_TIMESTAMP: &str = "2026-01-01T00:00:00.000000+00:00";
+/// Expiry far enough in the future for any test run
+pub const MOCK_EXPIRES_AT: &str = "2099-01-01T00:00:00.000000+00:00";
+
+/// A quest served by the mock, plus the user's progress on it
+///
+/// The mock mutates these as enroll / progress / heartbeat requests arrive and
+/// renders them in the REST (`snake_case`) or gateway (`camelCase`) shape.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuestFixture {
+    pub id: String,
+    pub name: String,
+    /// Task key in `task_config_v2.tasks`, e.g. `WATCH_VIDEO` or `PLAY_ON_DESKTOP`
+    pub task_type: String,
+    /// Seconds needed to complete the task
+    pub target: u64,
+    pub application_id: String,
+    pub application_name: String,
+    /// Seconds of progress recorded by the server
+    pub progress: f64,
+    pub enrolled: bool,
+    pub completed: bool,
+}
+
+impl QuestFixture {
+    pub fn new(id: &str, task_type: &str, target: u64) -> Self {
+        Self {
+            id: id.to_string(),
+            name: format!("Mock Quest {}", id),
+            task_type: task_type.to_string(),
+            target,
+            application_id: "1000000000000000000".to_string(),
+            application_name: "Mock Game".to_string(),
+            progress: 0.0,
+            enrolled: false,
+            completed: false,
+        }
+    }
+
+    pub fn enrolled(mut self) -> Self {
+        self.enrolled = true;
+        self
+    }
+
+    pub fn with_progress(mut self, seconds: f64) -> Self {
+        self.progress = seconds;
+        self
+    }
+
+    pub fn with_application(mut self, id: &str, name: &str) -> Self {
+        self.application_id = id.to_string();
+        self.application_name = name.to_string();
+        self
+    }
+
+    /// Record progress, completing the quest once the target is reached
+    pub(crate) fn record_progress(&mut self, seconds: f64) {
+        self.progress = seconds.min(self.target as f64);
+        if self.progress >= self.target as f64 {
+            self.completed = true;
+        }
+    }
+
+    /// Mark the quest as completed with full progress
+    pub(crate) fn complete(&mut self) {
+        self.enrolled = true;
+        self.record_progress(self.target as f64);
+    }
+
+    fn timestamp_if(flag: bool) -> Value {
+        if flag {
+            json!(MOCK_TIMESTAMP)
+        } else {
+            Value::Null
+        }
+    }
+
+    /// `user_status` object as returned by `/quests/@me` and the progress endpoints
+    pub fn user_status_json(&self) -> Value {
+        let mut progress = serde_json::Map::new();
+        if self.progress > 0.0 {
+            progress.insert(
+                self.task_type.clone(),
+                json!({
+                    "event_name": self.task_type,
+                    "value": self.progress,
+                    "updated_at": MOCK_TIMESTAMP,
+                    "completed_at": Self::timestamp_if(self.completed),
+                }),
+            );
+        }
+
+        json!({
+            "user_id": crate::MOCK_USER_ID,
+            "quest_id": self.id,
+            "enrolled_at": Self::timestamp_if(self.enrolled),
+            "completed_at": Self::timestamp_if(self.completed),
+            "claimed_at": null,
+            "progress": progress,
+        })
+    }
+
+    /// Quest object as returned by `/quests/@me`
+    pub fn rest_json(&self) -> Value {
+        json!({
+            "id": self.id,
+            "config": {
+                "id": self.id,
+                "config_version": 2,
+                "starts_at": MOCK_TIMESTAMP,
+                "expires_at": MOCK_EXPIRES_AT,
+                "application": {
+                    "id": self.application_id,
+                    "name": self.application_name,
+                    "link": "https://example.invalid",
+                },
+                "messages": {
+                    "quest_name": self.name,
+                    "game_title": self.application_name,
+                    "game_publisher": "Mock Publisher",
+                },
+                "task_config_v2": {
+                    "type": 1,
+                    "join_operator": "or",
+                    "tasks": {
+                        self.task_type.clone(): {
+                            "type": self.task_type,
+                            "target": self.target,
+                        }
+                    }
+                },
+                "rewards_config": {
+                    "rewards": [
+                        { "type": 3, "messages": { "name": "Mock Reward" } }
+                    ]
+                },
+            },
+            "user_status": if self.enrolled { self.user_status_json() } else { Value::Null },
+        })
+    }
+
+    /// Quest object as sent in the gateway READY payload
+    pub fn gateway_json(&self) -> Value {
+        let user_status = if self.enrolled {
+            let mut progress = serde_json::Map::new();
+            if self.progress > 0.0 {
+                progress.insert(
+                    self.task_type.clone(),
+                    json!({ "eventName": self.task_type, "value": self.progress }),
+                );
+            }
+            json!({
+                "enrolledAt": MOCK_TIMESTAMP,
+                "completedAt": Self::timestamp_if(self.completed),
+                "progress": progress,
+            })
+        } else {
+            Value::Null
+        };
+
+        json!({
+            "id": self.id,
+            "config": {
+                "expires_at": MOCK_EXPIRES_AT,
+                "messages": { "questName": self.name },
+                "application": { "id": self.application_id, "name": self.application_name },
+                "taskConfigV2": {
+                    "tasks": {
+                        self.task_type.clone(): { "type": self.task_type, "target": self.target }
+                    }
+                },
+            },
+            "user_status": user_status,
+        })
+    }
+}
@@ -0,0 +1,129 @@
+use axum::extract::ws::{CloseFrame, Message, WebSocket};
+use serde_json::{json, Value};
+
+use crate::SharedState;
+
+/// Close code Discord uses for a bad token in Identify
+const CLOSE_AUTHENTICATION_FAILED: u16 = 4004;
+
+/// How the mock gateway behaves for a session
+#[derive(Debug, Clone)]
+pub struct GatewayOptions {
+    /// `heartbeat_interval` sent in HELLO (milliseconds)
+    pub heartbeat_interval: u64,
+    /// Send quests in READY; otherwise they follow in READY_SUPPLEMENTAL
+    pub quests_in_ready: bool,
+    /// Answer Identify with op 7 (reconnect) instead of READY
+    pub request_reconnect: bool,
+}
+
+impl Default for GatewayOptions {
+    fn default() -> Self {
+        Self {
+            heartbeat_interval: 41250,
+            quests_in_ready: true,
+            request_reconnect: false,
+        }
+    }
+}
+
+async fn send_json(socket: &mut WebSocket, payload: Value) -> bool {
+    socket
+        .send(Message::Text(payload.to_string().into()))
+        .await
+        .is_ok()
+}
+
+fn dispatch(event: &str, sequence: u64, data: Value) -> Value {
+    json!({ "op": 0, "t": event, "s": sequence, "d": data })
+}
+
+/// One gateway connection: HELLO, wait for Identify, then READY (+ supplemental)
+pub(crate) async fn session(mut socket: WebSocket, state: SharedState) {
+    let options = state.lock().unwrap().gateway.clone();
+
+    let hello = json!({ "op": 10, "d": { "heartbeat_interval": options.heartbeat_interval } });
+    if !send_json(&mut socket, hello).await {
+        return;
+    }
+
+    let mut sequence = 0u64;
+
+    while let Some(Ok(message)) = socket.recv().await {
+        let text = match message {
+            Message::Text(text) => text.to_string(),
+            Message::Close(_) => return,
+            _ => continue,
+        };
+        let Ok(payload) = serde_json::from_str::<Value>(&text) else {
+            continue;
+        };
+
+        match payload.get("op").and_then(|op| op.as_u64()) {
+            Some(1) => {
+                let acked = send_json(&mut socket, json!({ "op": 11, "d": null })).await;
+                if !acked {
+                    return;
+                }
+            }
+            Some(2) => {
+                let token = payload
+                    .get("d")
+                    .and_then(|d| d.get("token"))
+                    .and_then(|t| t.as_str())
+                    .unwrap_or_default()
+                    .to_string();
+
+                let (user, quests, expected) = {
+                    let s = state.lock().unwrap();
+                    let quests: Vec<Value> = s.quests.iter().map(|q| q.gateway_json()).collect();
+                    (s.user.clone(), quests, s.expected_token.clone())
+                };
+
+                if expected.is_some_and(|expected| expected != token) {
+                    let _ = socket
+                        .send(Message::Close(Some(CloseFrame {
+                            code: CLOSE_AUTHENTICATION_FAILED,
+                            reason: "Authentication failed.".into(),
+                        })))
+                        .await;
+                    return;
+                }
+
+                if options.request_reconnect {
+                    let _ = send_json(&mut socket, json!({ "op": 7, "d": null })).await;
+                    continue;
+                }
+
+                sequence += 1;
+                let mut ready = json!({
+                    "v": 9,
+                    "user": user,
+                    "session_id": "mock-session",
+                    "guilds": [],
+                });
+                if options.quests_in_ready {
+                    ready["quests"] = Value::Array(quests.clone());
+                }
+                if !send_json(&mut socket, dispatch("READY", sequence, ready)).await {
+                    return;
+                }
+
+                sequence += 1;
+                let mut supplemental = json!({ "guilds": [], "merged_presences": {} });
+                if !options.quests_in_ready {
+                    supplemental["quests"] = Value::Array(quests);
+                }
+                if !send_json(
+                    &mut socket,
+                    dispatch("READY_SUPPLEMENTAL", sequence, supplemental),
+                )
+                .await
+                {
+                    return;
+                }
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,427 @@
+//! Local stand-in for the Discord REST API and gateway
+//!
+//! Serves the handful of endpoints the quest helper uses on an ephemeral
+//! localhost port, with per-route scripted failures (401, 429, 5xx, ...) so the
+//! API client, completers and gateway can be tested end-to-end without a token.
+
+mod fixtures;
+mod gateway;
+mod script;
+
+pub use fixtures::{QuestFixture, MOCK_EXPIRES_AT, MOCK_TIMESTAMP};
+pub use gateway::GatewayOptions;
+pub use script::{Route, ScriptedResponse};
+
+use axum::extract::{Path, State, WebSocketUpgrade};
+use axum::http::{HeaderMap, Method, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use serde_json::{json, Value};
+use std::collections::{HashMap, VecDeque};
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use tokio::sync::oneshot;
+
+/// Token the mock accepts by default
+pub const MOCK_TOKEN: &str = "mock-token.XXXXXX.mock-secret-value";
+/// Id of the mock user
+pub const MOCK_USER_ID: &str = "100000000000000001";
+
+/// A request received by the mock
+#[derive(Debug, Clone)]
+pub struct RecordedRequest {
+    pub method: Method,
+    pub route: Route,
+    pub path: String,
+    pub quest_id: Option<String>,
+    pub authorization: Option<String>,
+    pub body: Value,
+}
+
+struct MockState {
+    user: Value,
+    expected_token: Option<String>,
+    quests: Vec<QuestFixture>,
+    detectable: Value,
+    scripts: HashMap<Route, VecDeque<ScriptedResponse>>,
+    requests: Vec<RecordedRequest>,
+    heartbeat_increment: f64,
+    gateway: GatewayOptions,
+}
+
+type SharedState = Arc<Mutex<MockState>>;
+
+/// Running mock server; shut down when dropped
+pub struct MockDiscord {
+    addr: SocketAddr,
+    state: SharedState,
+    shutdown: Option<oneshot::Sender<()>>,
+}
+
+impl MockDiscord {
+    /// Start the mock on an ephemeral localhost port
+    pub async fn start() -> Self {
+        let state: SharedState = Arc::new(Mutex::new(MockState {
+            user: json!({
+                "id": MOCK_USER_ID,
+                "username": "mockuser",
+                "discriminator": "0",
+                "avatar": null,
+                "global_name": "Mock User",
+            }),
+            expected_token: Some(MOCK_TOKEN.to_string()),
+            quests: Vec::new(),
+            detectable: json!([]),
+            scripts: HashMap::new(),
+            requests: Vec::new(),
+            heartbeat_increment: 60.0,
+            gateway: GatewayOptions::default(),
+        }));
+
+        let app = Router::new()
+            .route("/api/{version}/users/@me", get(current_user))
+            .route("/api/{version}/quests/@me", get(quests))
+            .route("/api/{version}/quests/{quest_id}/enroll", post(enroll))
+            .route(
+                "/api/{version}/quests/{quest_id}/video-progress",
+                post(video_progress),
+            )
+            .route(
+                "/api/{version}/quests/{quest_id}/heartbeat",
+                post(heartbeat),
+            )
+            .route("/api/{version}/applications/detectable", get(detectable))
+            .route("/gateway", get(gateway_upgrade))
+            .route("/gateway/", get(gateway_upgrade))
+            .with_state(state.clone());
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("could not bind mock Discord server");
+        let addr = listener
+            .local_addr()
+            .expect("mock server has no local address");
+        let (shutdown_tx, shutdown_rx) = oneshot::channel::<()>();
+
+        tokio::spawn(async move {
+            let _ = axum::serve(listener, app)
+                .with_graceful_shutdown(async {
+                    let _ = shutdown_rx.await;
+                })
+                .await;
+        });
+
+        Self {
+            addr,
+            state,
+            shutdown: Some(shutdown_tx),
+        }
+    }
+
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// REST base without the version segment, e.g. `http://127.0.0.1:PORT/api`
+    pub fn rest_base(&self) -> String {
+        format!("http://{}/api", self.addr)
+    }
+
+    /// Gateway URL, e.g. `ws://127.0.0.1:PORT/gateway`
+    pub fn gateway_url(&self) -> String {
+        format!("ws://{}/gateway", self.addr)
+    }
+
+    fn with_state<T>(&self, f: impl FnOnce(&mut MockState) -> T) -> T {
+        f(&mut self.state.lock().unwrap())
+    }
+
+    /// Replace the `/users/@me` body
+    pub fn set_user(&self, user: Value) {
+        self.with_state(|s| s.user = user);
+    }
+
+    /// Require this token (`None` accepts any Authorization header)
+    pub fn expect_token(&self, token: Option<&str>) {
+        self.with_state(|s| s.expected_token = token.map(|t| t.to_string()));
+    }
+
+    pub fn add_quest(&self, quest: QuestFixture) {
+        self.with_state(|s| s.quests.push(quest));
+    }
+
+    /// Current server-side state of a quest
+    pub fn quest(&self, quest_id: &str) -> Option<QuestFixture> {
+        self.with_state(|s| s.quests.iter().find(|q| q.id == quest_id).cloned())
+    }
+
+    /// Replace the `/applications/detectable` body
+    pub fn set_detectable_games(&self, games: Value) {
+        self.with_state(|s| s.detectable = games);
+    }
+
+    /// Seconds of progress credited per (non-terminal) heartbeat
+    pub fn set_heartbeat_increment(&self, seconds: f64) {
+        self.with_state(|s| s.heartbeat_increment = seconds);
+    }
+
+    pub fn set_gateway_options(&self, options: GatewayOptions) {
+        self.with_state(|s| s.gateway = options);
+    }
+
+    /// Queue a one-shot response for the next request to `route`
+    pub fn script(&self, route: Route, response: ScriptedResponse) {
+        self.with_state(|s| s.scripts.entry(route).or_default().push_back(response));
+    }
+
+    /// All requests received so far
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.with_state(|s| s.requests.clone())
+    }
+
+    /// Requests received for one route
+    pub fn requests_to(&self, route: Route) -> Vec<RecordedRequest> {
+        self.with_state(|s| {
+            s.requests
+                .iter()
+                .filter(|r| r.route == route)
+                .cloned()
+                .collect()
+        })
+    }
+}
+
+impl Drop for MockDiscord {
+    fn drop(&mut self) {
+        if let Some(shutdown) = self.shutdown.take() {
+            let _ = shutdown.send(());
+        }
+    }
+}
+
+/// Shared request handling: record, authenticate, apply scripts, then run the route
+fn handle(
+    state: &SharedState,
+    headers: &HeaderMap,
+    mut request: RecordedRequest,
+    default: impl FnOnce(&mut MockState) -> Response,
+) -> Response {
+    let mut s = state.lock().unwrap();
+
+    let authorization = headers
+        .get("authorization")
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.to_string());
+    request.authorization = authorization.clone();
+
+    let route = request.route;
+    let quest_id = request.quest_id.clone();
+    s.requests.push(request);
+
+    if let Some(expected) = &s.expected_token {
+        if authorization.as_deref() != Some(expected.as_str()) {
+            return ScriptedResponse::Unauthorized.into_response();
+        }
+    }
+
+    match s
+        .scripts
+        .get_mut(&route)
+        .and_then(|queue| queue.pop_front())
+    {
+        Some(ScriptedResponse::Complete) => {
+            let quest_id = quest_id.unwrap_or_default();
+            match s.quests.iter_mut().find(|q| q.id == quest_id) {
+                Some(quest) => {
+                    quest.complete();
+                    Json(quest.user_status_json()).into_response()
+                }
+                None => ScriptedResponse::NotFound.into_response(),
+            }
+        }
+        Some(scripted) => scripted.into_response(),
+        None => default(&mut s),
+    }
+}
+
+fn request(
+    method: Method,
+    route: Route,
+    path: String,
+    quest_id: Option<String>,
+    body: Value,
+) -> RecordedRequest {
+    RecordedRequest {
+        method,
+        route,
+        path,
+        quest_id,
+        authorization: None,
+        body,
+    }
+}
+
+fn parse_body(body: &str) -> Value {
+    serde_json::from_str(body).unwrap_or(Value::Null)
+}
+
+fn find_quest<'a>(s: &'a mut MockState, quest_id: &str) -> Option<&'a mut QuestFixture> {
+    s.quests.iter_mut().find(|q| q.id == quest_id)
+}
+
+async fn current_user(
+    State(state): State<SharedState>,
+    Path(version): Path<String>,
+    headers: HeaderMap,
+) -> Response {
+    let path = format!("/api/{}/users/@me", version);
+    handle(
+        &state,
+        &headers,
+        request(Method::GET, Route::CurrentUser, path, None, Value::Null),
+        |s| Json(s.user.clone()).into_response(),
+    )
+}
+
+async fn quests(
+    State(state): State<SharedState>,
+    Path(version): Path<String>,
+    headers: HeaderMap,
+) -> Response {
+    let path = format!("/api/{}/quests/@me", version);
+    handle(
+        &state,
+        &headers,
+        request(Method::GET, Route::Quests, path, None, Value::Null),
+        |s| {
+            let quests: Vec<Value> = s.quests.iter().map(|q| q.rest_json()).collect();
+            Json(json!({ "quests": quests, "excluded_quests": [], "quest_enrollment_blocked_until": null }))
+            .into_response()
+        },
+    )
+}
+
+async fn enroll(
+    State(state): State<SharedState>,
+    Path((version, quest_id)): Path<(String, String)>,
+    headers: HeaderMap,
+    body: String,
+) -> Response {
+    let path = format!("/api/{}/quests/{}/enroll", version, quest_id);
+    let id = quest_id.clone();
+    handle(
+        &state,
+        &headers,
+        request(
+            Method::POST,
+            Route::Enroll,
+            path,
+            Some(quest_id),
+            parse_body(&body),
+        ),
+        |s| match find_quest(s, &id) {
+            Some(quest) => {
+                quest.enrolled = true;
+                Json(quest.user_status_json()).into_response()
+            }
+            None => ScriptedResponse::NotFound.into_response(),
+        },
+    )
+}
+
+async fn video_progress(
+    State(state): State<SharedState>,
+    Path((version, quest_id)): Path<(String, String)>,
+    headers: HeaderMap,
+    body: String,
+) -> Response {
+    let path = format!("/api/{}/quests/{}/video-progress", version, quest_id);
+    let id = quest_id.clone();
+    let payload = parse_body(&body);
+    let timestamp = payload.get("timestamp").and_then(|t| t.as_f64());
+    handle(
+        &state,
+        &headers,
+        request(
+            Method::POST,
+            Route::VideoProgress,
+            path,
+            Some(quest_id),
+            payload,
+        ),
+        |s| {
+            let Some(timestamp) = timestamp else {
+                return script::discord_error(StatusCode::BAD_REQUEST, 50035, "Invalid Form Body");
+            };
+            match find_quest(s, &id) {
+                Some(quest) if quest.enrolled => {
+                    // Discord never moves progress backwards
+                    let seconds = quest.progress.max(timestamp);
+                    quest.record_progress(seconds);
+                    Json(quest.user_status_json()).into_response()
+                }
+                Some(_) => script::discord_error(StatusCode::BAD_REQUEST, 0, "Quest not enrolled"),
+                None => ScriptedResponse::NotFound.into_response(),
+            }
+        },
+    )
+}
+
+async fn heartbeat(
+    State(state): State<SharedState>,
+    Path((version, quest_id)): Path<(String, String)>,
+    headers: HeaderMap,
+    body: String,
+) -> Response {
+    let path = format!("/api/{}/quests/{}/heartbeat", version, quest_id);
+    let id = quest_id.clone();
+    let payload = parse_body(&body);
+    let terminal = payload
+        .get("terminal")
+        .and_then(|t| t.as_bool())
+        .unwrap_or(false);
+    handle(
+        &state,
+        &headers,
+        request(
+            Method::POST,
+            Route::Heartbeat,
+            path,
+            Some(quest_id),
+            payload,
+        ),
+        |s| {
+            let increment = s.heartbeat_increment;
+            match find_quest(s, &id) {
+                Some(quest) if quest.enrolled => {
+                    if !terminal {
+                        let seconds = quest.progress + increment;
+                        quest.record_progress(seconds);
+                    }
+                    Json(quest.user_status_json()).into_response()
+                }
+                Some(_) => script::discord_error(StatusCode::BAD_REQUEST, 0, "Quest not enrolled"),
+                None => ScriptedResponse::NotFound.into_response(),
+            }
+        },
+    )
+}
+
+async fn detectable(
+    State(state): State<SharedState>,
+    Path(version): Path<String>,
+    headers: HeaderMap,
+) -> Response {
+    let path = format!("/api/{}/applications/detectable", version);
+    handle(
+        &state,
+        &headers,
+        request(Method::GET, Route::Detectable, path, None, Value::Null),
+        |s| Json(s.detectable.clone()).into_response(),
+    )
+}
+
+async fn gateway_upgrade(State(state): State<SharedState>, ws: WebSocketUpgrade) -> Response {
+    ws.on_upgrade(move |socket| gateway::session(socket, state))
+}
@@ -0,0 +1,115 @@
+use axum::http::{HeaderValue, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use serde_json::{json, Value};
+
+/// REST routes served by the mock
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Route {
+    /// `GET /users/@me`
+    CurrentUser,
+    /// `GET /quests/@me`
+    Quests,
+    /// `POST /quests/{id}/enroll`
+    Enroll,
+    /// `POST /quests/{id}/video-progress`
+    VideoProgress,
+    /// `POST /quests/{id}/heartbeat`
+    Heartbeat,
+    /// `GET /applications/detectable`
+    Detectable,
+}
+
+/// One-shot response override for a route
+///
+/// Scripted responses are queued per route and consumed in order; once the queue
+/// is empty the route falls back to its normal behaviour.
+#[derive(Debug, Clone)]
+pub enum ScriptedResponse {
+    /// 401 with Discord's error body
+    Unauthorized,
+    /// 403 with Discord's error body
+    Forbidden,
+    /// 404 with Discord's error body
+    NotFound,
+    /// 429 with `Retry-After` / `X-RateLimit-*` headers
+    RateLimited { retry_after: f64, global: bool },
+    /// Any 5xx (or other) status with a generic error body
+    ServerError(u16),
+    /// Mark the quest as completed and answer as if the target was reached
+    Complete,
+    /// Arbitrary status and JSON body
+    Json(u16, Value),
+}
+
+pub(crate) fn discord_error(status: StatusCode, code: u32, message: &str) -> Response {
+    (status, Json(json!({ "message": message, "code": code }))).into_response()
+}
+
+pub(crate) fn rate_limited(retry_after: f64, global: bool) -> Response {
+    let mut response = (
+        StatusCode::TOO_MANY_REQUESTS,
+        Json(json!({
+            "message": "You are being rate limited.",
+            "retry_after": retry_after,
+            "global": global,
+        })),
+    )
+        .into_response();
+
+    let headers = response.headers_mut();
+    let retry_secs = retry_after.ceil().max(0.0) as u64;
+    headers.insert("retry-after", HeaderValue::from(retry_secs));
+    headers.insert("x-ratelimit-limit", HeaderValue::from_static("5"));
+    headers.insert("x-ratelimit-remaining", HeaderValue::from_static("0"));
+    headers.insert(
+        "x-ratelimit-reset-after",
+        HeaderValue::from_str(&format!("{:.3}", retry_after)).unwrap(),
+    );
+    headers.insert(
+        "x-ratelimit-bucket",
+        HeaderValue::from_static("mock-bucket"),
+    );
+    if global {
+        headers.insert("x-ratelimit-global", HeaderValue::from_static("true"));
+    } else {
+        headers.insert("x-ratelimit-scope", HeaderValue::from_static("user"));
+    }
+    response
+}
+
+impl ScriptedResponse {
+    /// Render anything but `Complete`, which needs the quest state
+    pub(crate) fn into_response(self) -> Response {
+        match self {
+            ScriptedResponse::Unauthorized => {
+                discord_error(StatusCode::UNAUTHORIZED, 0, "401: Unauthorized")
+            }
+            ScriptedResponse::Forbidden => {
+                discord_error(StatusCode::FORBIDDEN, 50001, "Missing Access")
+            }
+            ScriptedResponse::NotFound => {
+                discord_error(StatusCode::NOT_FOUND, 10070, "Unknown Quest")
+            }
+            ScriptedResponse::RateLimited {
+                retry_after,
+                global,
+            } => rate_limited(retry_after, global),
+            ScriptedResponse::ServerError(status) => discord_error(
+                StatusCode::from_u16(status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR),
+                0,
+                "Internal Server Error",
+            ),
+            ScriptedResponse::Json(status, body) => (
+                StatusCode::from_u16(status).unwrap_or(StatusCode::OK),
+                Json(body),
+            )
+                .into_response(),
+            ScriptedResponse::Complete => discord_error(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                0,
+                "Complete needs a quest route",
+            ),
+        }
+    }
+}
@@ -0,0 +1,65 @@
+//! Tokens of detected accounts, kept away from the frontend
+//!
+//! The UI only ever sees an opaque handle per account; the token behind it
+//! stays in this store for as long as the process runs.
+
+use crate::secret::SecretString;
+use rand::distr::Alphanumeric;
+use rand::Rng;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Tokens keyed by opaque account handles
+#[derive(Default)]
+pub struct AccountStore {
+    tokens: Mutex<HashMap<String, SecretString>>,
+}
+
+pub(crate) fn new_handle() -> String {
+    let random: String = rand::rng()
+        .sample_iter(Alphanumeric)
+        .take(24)
+        .map(char::from)
+        .collect();
+    format!("acct_{}", random)
+}
+
+impl AccountStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Handle for `token`; adding the same token again returns the same handle
+    pub fn add(&self, token: SecretString) -> String {
+        let mut tokens = self.tokens.lock().unwrap();
+        if let Some((handle, _)) = tokens.iter().find(|(_, t)| t.expose_secret() == token.expose_secret()) {
+            return handle.clone();
+        }
+        let handle = new_handle();
+        tokens.insert(handle.clone(), token);
+        handle
+    }
+
+    /// Token behind `handle`, if it came from [`AccountStore::add`]
+    pub fn token(&self, handle: &str) -> Option<SecretString> {
+        self.tokens.lock().unwrap().get(handle).cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_handles_are_opaque_and_stable() {
+        let store = AccountStore::new();
+        let first = store.add("first-token".into());
+        let second = store.add("second-token".into());
+
+        assert_ne!(first, second);
+        assert!(!first.contains("first-token"));
+        assert_eq!(store.add("first-token".into()), first);
+        assert_eq!(store.token(&first).unwrap().expose_secret(), "first-token");
+        assert!(store.token("acct_unknown").is_none());
+    }
+}
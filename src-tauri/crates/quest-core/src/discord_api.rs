@@ -0,0 +1,417 @@
+use crate::endpoints::DiscordEndpoints;
+use crate::error::{ApiResult, DiscordApiError};
+use crate::models::*;
+use crate::ratelimit::{server_delay, RateLimiter, MAX_AUTO_RETRY_AFTER, MAX_RATE_LIMIT_RETRIES};
+use crate::secret::SecretString;
+use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION, CONTENT_TYPE, USER_AGENT};
+use std::sync::Arc;
+use tracing::{debug, info, instrument, warn, Span};
+
+/// Discord API client
+#[derive(Clone)]
+pub struct DiscordApiClient {
+    client: Arc<reqwest::Client>,
+    token: SecretString,
+    endpoints: DiscordEndpoints,
+    /// Shared by all clones so throttling applies across quest loops
+    rate_limiter: Arc<RateLimiter>,
+}
+
+impl DiscordApiClient {
+    /// Create a new API client talking to the given endpoints
+    pub fn new(token: impl Into<SecretString>, endpoints: DiscordEndpoints) -> ApiResult<Self> {
+        let token = token.into();
+        let mut authorization = HeaderValue::from_str(token.expose_secret()).map_err(|_| DiscordApiError::Unauthorized {
+            message: "Invalid token format".to_string(),
+        })?;
+        // Keeps it out of reqwest's debug output
+        authorization.set_sensitive(true);
+
+        let mut headers = HeaderMap::new();
+        headers.insert(AUTHORIZATION, authorization);
+        headers.insert(
+            CONTENT_TYPE,
+            HeaderValue::from_static("application/json"),
+        );
+        headers.insert(
+            USER_AGENT,
+            HeaderValue::from_static("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) discord/1.0.9219 Chrome/138.0.7204.251 Electron/37.6.0 Safari/537.36"),
+        );
+        // X-Super-Properties from HAR capture (client_build_number: 481287)
+        headers.insert(
+            "x-super-properties",
+            HeaderValue::from_static("eyJvcyI6IldpbmRvd3MiLCJicm93c2VyIjoiRGlzY29yZCBDbGllbnQiLCJyZWxlYXNlX2NoYW5uZWwiOiJzdGFibGUiLCJjbGllbnRfdmVyc2lvbiI6IjEuMC45MjE5Iiwib3NfdmVyc2lvbiI6IjEwLjAuMTkwNDUiLCJvc19hcmNoIjoieDY0IiwiYXBwX2FyY2giOiJ4NjQiLCJzeXN0ZW1fbG9jYWxlIjoiZW4tVVMiLCJoYXNfY2xpZW50X21vZHMiOmZhbHNlLCJicm93c2VyX3VzZXJfYWdlbnQiOiJNb3ppbGxhLzUuMCAoV2luZG93cyBOVCAxMC4wOyBXaW42NDsgeDY0KSBBcHBsZVdlYktpdC81MzcuMzYgKEtIVE1MLCBsaWtlIEdlY2tvKSBkaXNjb3JkLzEuMC45MjE5IENocm9tZS8xMzguMC43MjA0LjI1MSBFbGVjdHJvbi8zNy42LjAgU2FmYXJpLzUzNy4zNiIsImJyb3dzZXJfdmVyc2lvbiI6IjM3LjYuMCIsIm9zX3Nka192ZXJzaW9uIjoiMTkwNDUiLCJjbGllbnRfYnVpbGRfbnVtYmVyIjo0ODEyODcsIm5hdGl2ZV9idWlsZF9udW1iZXIiOjczMjExLCJjbGllbnRfZXZlbnRfc291cmNlIjpudWxsfQ=="),
+        );
+        headers.insert(
+            "x-discord-timezone",
+            HeaderValue::from_static("America/Los_Angeles"),
+        );
+        headers.insert(
+            "x-discord-locale",
+            HeaderValue::from_static("en-US"),
+        );
+        headers.insert(
+            "x-debug-options",
+            HeaderValue::from_static("bugReporterEnabled"),
+        );
+        headers.insert(
+            "accept",
+            HeaderValue::from_static("*/*"),
+        );
+
+        let client = reqwest::Client::builder()
+            .default_headers(headers)
+            .build()
+            .map_err(|e| DiscordApiError::transport("Could not create HTTP client", e))?;
+
+        Ok(Self {
+            client: Arc::new(client),
+            token,
+            endpoints,
+            rate_limiter: Arc::new(RateLimiter::new()),
+        })
+    }
+
+    pub fn get_token(&self) -> &SecretString {
+        &self.token
+    }
+
+    /// Endpoints this client was created with
+    pub fn endpoints(&self) -> &DiscordEndpoints {
+        &self.endpoints
+    }
+
+    /// Send a request, turning transport failures and non-success statuses into typed errors
+    ///
+    /// Waits out known per-route / global rate limits before sending, and retries
+    /// short 429s (up to `MAX_RATE_LIMIT_RETRIES`) instead of failing the call.
+    /// Everything logged meanwhile is inside a `request` span with the action,
+    /// route and final status.
+    #[instrument(name = "request", skip_all, fields(action = action, route, status))]
+    async fn send(&self, request: reqwest::RequestBuilder, action: &str) -> ApiResult<reqwest::Response> {
+        let request = request
+            .build()
+            .map_err(|e| DiscordApiError::transport(action, e))?;
+        let route = format!("{} {}", request.method(), request.url().path());
+        Span::current().record("route", route.as_str());
+
+        let mut attempt = 0;
+        loop {
+            self.rate_limiter.wait(&route).await;
+
+            // JSON bodies are always clonable
+            let this_request = request.try_clone().expect("request body is not clonable");
+            let response = self
+                .client
+                .execute(this_request)
+                .await
+                .map_err(|e| DiscordApiError::transport(action, e))?;
+
+            self.rate_limiter.update(&route, response.headers());
+
+            let status = response.status();
+            Span::current().record("status", status.as_u16());
+            if status.is_success() {
+                debug!("{} succeeded", action);
+                return Ok(response);
+            }
+
+            let retry_after = response
+                .headers()
+                .get("retry-after")
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.parse::<f64>().ok())
+                .filter(|v| v.is_finite());
+            let body = response.text().await.unwrap_or_default();
+            warn!("{} failed: {} - {}", action, status, body);
+
+            let error = DiscordApiError::from_status(status, retry_after, &body);
+            if let DiscordApiError::RateLimited { retry_after, global, .. } = &error {
+                self.rate_limiter.limited(&route, *retry_after, *global);
+
+                attempt += 1;
+                let wait = server_delay(*retry_after);
+                if attempt <= MAX_RATE_LIMIT_RETRIES && wait <= MAX_AUTO_RETRY_AFTER {
+                    warn!(
+                        "{} rate limited (global={}), retrying after {:.2}s ({}/{})",
+                        action, global, retry_after, attempt, MAX_RATE_LIMIT_RETRIES
+                    );
+                    continue;
+                }
+            }
+
+            return Err(error);
+        }
+    }
+
+    /// Read the body and parse it as JSON
+    async fn decode<T: serde::de::DeserializeOwned>(response: reqwest::Response, action: &str) -> ApiResult<T> {
+        let body = response
+            .text()
+            .await
+            .map_err(|e| DiscordApiError::transport(action, e))?;
+        serde_json::from_str(&body).map_err(|e| DiscordApiError::decode(action, e))
+    }
+
+    /// Get current user info
+    pub async fn get_current_user(&self) -> ApiResult<DiscordUser> {
+        let url = self.endpoints.rest_url("/users/@me");
+
+        let response = self.send(self.client.get(&url), "Get user info").await?;
+        Self::decode(response, "Parse user info").await
+    }
+
+    /// Get the quest list (via /quests/@me endpoint)
+    ///
+    /// Entries that don't parse are logged and skipped rather than failing the whole list.
+    pub async fn get_quests(&self) -> ApiResult<Vec<Quest>> {
+        let url = self.endpoints.rest_url("/quests/@me");
+        
+        debug!("Requesting quest list: {}", url);
+        
+        let response = self.send(self.client.get(&url), "Get quest list").await?;
+        let data: QuestsResponse = Self::decode(response, "Parse quest list").await?;
+
+        let quests = parse_quests(&data.quests);
+
+        info!("Successfully retrieved {} quests", quests.len());
+        Ok(quests)
+    }
+
+    /// Update video watch progress
+    ///
+    /// Returns the quest's `user_status` as Discord now sees it.
+    pub async fn update_video_progress(
+        &self,
+        quest_id: &str,
+        timestamp: f64,
+    ) -> ApiResult<QuestUserStatus> {
+        let url = self.endpoints.rest_url(&format!("/quests/{}/video-progress", quest_id));
+        
+        let payload = VideoProgressPayload {
+            timestamp,
+        };
+
+        debug!("Sending video progress: quest_id={}, timestamp={:.1}", quest_id, timestamp);
+
+        let response = self
+            .send(self.client.post(&url).json(&payload), "Update video progress")
+            .await?;
+        Self::decode(response, "Parse video progress").await
+    }
+
+    /// Send stream heartbeat
+    ///
+    /// Returns the quest's `user_status` as Discord now sees it.
+    pub async fn send_stream_heartbeat(
+        &self,
+        quest_id: &str,
+        stream_key: &str,
+    ) -> ApiResult<QuestUserStatus> {
+        let url = self.endpoints.rest_url(&format!("/quests/{}/heartbeat", quest_id));
+        
+        let payload = HeartbeatPayload {
+            stream_key: stream_key.to_string(),
+        };
+
+        let response = self
+            .send(self.client.post(&url).json(&payload), "Send heartbeat")
+            .await?;
+        Self::decode(response, "Parse heartbeat").await
+    }
+
+    /// Send game heartbeat (for PLAY_ON_DESKTOP quests without running actual game)
+    ///
+    /// Returns the quest's `user_status` as Discord now sees it.
+    pub async fn send_game_heartbeat(
+        &self,
+        quest_id: &str,
+        application_id: &str,
+        terminal: bool,
+    ) -> ApiResult<QuestUserStatus> {
+        let url = self.endpoints.rest_url(&format!("/quests/{}/heartbeat", quest_id));
+        
+        let payload = GameHeartbeatPayload {
+            application_id: application_id.to_string(),
+            terminal,
+        };
+
+        debug!("Sending game heartbeat: quest_id={}, app_id={}, terminal={}", quest_id, application_id, terminal);
+
+        let response = self
+            .send(self.client.post(&url).json(&payload), "Send game heartbeat")
+            .await?;
+        Self::decode(response, "Parse game heartbeat").await
+    }
+
+    /// Accept quest (enroll in quest)
+    pub async fn accept_quest(&self, quest_id: &str) -> ApiResult<serde_json::Value> {
+        let url = self.endpoints.rest_url(&format!("/quests/{}/enroll", quest_id));
+        
+        info!("Accepting quest: quest_id={}", quest_id);
+
+        // POST with enrollment payload from HAR capture
+        let payload = serde_json::json!({
+            "location": 11,
+            "is_targeted": false,
+            "metadata_raw": null
+        });
+
+        let response = self
+            .send(self.client.post(&url).json(&payload), "Accept quest")
+            .await?;
+
+        let body: serde_json::Value = response.json().await.unwrap_or_default();
+        debug!("Quest accepted successfully: {:?}", body);
+        
+        Ok(body)
+    }
+
+    /// Get detectable games list
+    pub async fn fetch_detectable_games(&self) -> ApiResult<Vec<DetectableGame>> {
+        let url = self.endpoints.rest_url("/applications/detectable");
+        
+        let response = self
+            .send(self.client.get(&url), "Get detectable games list")
+            .await?;
+        Self::decode(response, "Parse games list").await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{mock_client, mock_client_with_token};
+    use mock_discord::{MockDiscord, QuestFixture, Route, ScriptedResponse, MOCK_TOKEN};
+
+
+    #[tokio::test]
+    async fn test_get_current_user_from_mock() {
+        let mock = MockDiscord::start().await;
+        let user = mock_client(&mock).get_current_user().await.unwrap();
+
+        assert_eq!(user.username, "mockuser");
+        let requests = mock.requests_to(Route::CurrentUser);
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].path, "/api/v9/users/@me");
+        assert_eq!(requests[0].authorization.as_deref(), Some(MOCK_TOKEN));
+    }
+
+    #[tokio::test]
+    async fn test_invalid_token_is_rejected() {
+        let mock = MockDiscord::start().await;
+        let client = mock_client_with_token(&mock, "wrong-token");
+
+        let err = client.get_current_user().await.unwrap_err();
+        assert!(matches!(err, DiscordApiError::Unauthorized { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_accept_quest_and_list() {
+        let mock = MockDiscord::start().await;
+        mock.add_quest(QuestFixture::new("1", "WATCH_VIDEO", 900));
+        let client = mock_client(&mock);
+
+        client.accept_quest("1").await.unwrap();
+        let quests = client.get_quests().await.unwrap();
+
+        assert_eq!(quests.len(), 1);
+        assert!(quests[0].is_enrolled());
+        assert_eq!(quests[0].tasks()["WATCH_VIDEO"].target, 900);
+        assert_eq!(quests[0].reward_names(), vec!["Mock Reward"]);
+    }
+
+    #[tokio::test]
+    async fn test_video_progress_reports_completion() {
+        let mock = MockDiscord::start().await;
+        mock.add_quest(QuestFixture::new("1", "WATCH_VIDEO", 30).enrolled());
+        let client = mock_client(&mock);
+
+        let status = client.update_video_progress("1", 10.0).await.unwrap();
+        assert!(!status.is_completed());
+        assert_eq!(status.task_progress("WATCH_VIDEO"), Some(10.0));
+        assert!(client.update_video_progress("1", 30.0).await.unwrap().is_completed());
+        assert!(mock.quest("1").unwrap().completed);
+    }
+
+    #[tokio::test]
+    async fn test_scripted_failures_surface_as_errors() {
+        let mock = MockDiscord::start().await;
+        mock.add_quest(QuestFixture::new("1", "PLAY_ON_DESKTOP", 900).enrolled());
+        mock.script(Route::Heartbeat, ScriptedResponse::ServerError(502));
+        mock.script(Route::Heartbeat, ScriptedResponse::Complete);
+        let client = mock_client(&mock);
+
+        let err = client.send_game_heartbeat("1", "123", false).await.unwrap_err();
+        assert!(matches!(err, DiscordApiError::DiscordError { status: 502, .. }));
+        assert!(client.send_game_heartbeat("1", "123", false).await.unwrap().is_completed());
+    }
+
+    #[tokio::test]
+    async fn test_error_kinds() {
+        let mock = MockDiscord::start().await;
+        mock.add_quest(QuestFixture::new("1", "WATCH_VIDEO", 900).enrolled());
+        mock.script(Route::VideoProgress, ScriptedResponse::RateLimited { retry_after: 120.0, global: false });
+        mock.script(Route::VideoProgress, ScriptedResponse::Forbidden);
+        let client = mock_client(&mock);
+
+        // Too long to wait out automatically
+        let err = client.update_video_progress("1", 10.0).await.unwrap_err();
+        assert!(matches!(err, DiscordApiError::RateLimited { retry_after, .. } if retry_after == 120.0));
+        // Quest 1's route is still throttled, so hit another route for the scripted 403
+        let err = client.update_video_progress("2", 10.0).await.unwrap_err();
+        assert!(matches!(err, DiscordApiError::Forbidden { .. }));
+        let err = client.accept_quest("missing").await.unwrap_err();
+        assert_eq!(err.kind(), "not_found");
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_short_rate_limit_is_retried() {
+        let mock = MockDiscord::start().await;
+        mock.add_quest(QuestFixture::new("1", "PLAY_ON_DESKTOP", 900).enrolled());
+        mock.script(Route::Heartbeat, ScriptedResponse::RateLimited { retry_after: 2.0, global: false });
+        mock.script(Route::Heartbeat, ScriptedResponse::RateLimited { retry_after: 2.0, global: true });
+        let client = mock_client(&mock);
+
+        let started = tokio::time::Instant::now();
+        assert!(!client.send_game_heartbeat("1", "123", false).await.unwrap().is_completed());
+
+        assert_eq!(mock.requests_to(Route::Heartbeat).len(), 3);
+        assert!(started.elapsed() >= std::time::Duration::from_secs(4));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_rate_limit_retries_are_bounded() {
+        let mock = MockDiscord::start().await;
+        mock.add_quest(QuestFixture::new("1", "PLAY_ON_DESKTOP", 900).enrolled());
+        for _ in 0..=MAX_RATE_LIMIT_RETRIES {
+            mock.script(Route::Heartbeat, ScriptedResponse::RateLimited { retry_after: 1.0, global: false });
+        }
+        let client = mock_client(&mock);
+
+        let err = client.send_game_heartbeat("1", "123", false).await.unwrap_err();
+        assert_eq!(err.kind(), "rate_limited");
+        assert_eq!(mock.requests_to(Route::Heartbeat).len(), MAX_RATE_LIMIT_RETRIES as usize + 1);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_detectable_games_from_mock() {
+        let mock = MockDiscord::start().await;
+        mock.set_detectable_games(serde_json::json!([
+            { "id": "42", "name": "Mock Game", "executables": [{ "name": "mock.exe", "os": "win32" }] }
+        ]));
+
+        let games = mock_client(&mock).fetch_detectable_games().await.unwrap();
+        assert_eq!(games.len(), 1);
+        assert_eq!(games[0].executables[0].name, "mock.exe");
+    }
+
+    #[tokio::test]
+    #[ignore] // Requires valid token
+    async fn test_get_current_user() {
+        let token = "YOUR_TOKEN_HERE";
+        let client = DiscordApiClient::new(token.to_string(), DiscordEndpoints::default()).unwrap();
+        let user = client.get_current_user().await.unwrap();
+        println!("User: {:?}", user);
+    }
+}
@@ -3,11 +3,11 @@ use futures_util::{SinkExt, StreamExt};
 use serde::Deserialize;
 use serde_json::{json, Value};
 use tokio_tungstenite::{connect_async, tungstenite::Message};
+use tracing::{debug, info, instrument, warn};
 
-use crate::models::Quest;
-
-#[allow(dead_code)]
-const GATEWAY_URL: &str = "wss://gateway.discord.gg/?v=9&encoding=json&compress=zlib-stream";
+use crate::endpoints::DiscordEndpoints;
+use crate::models::{parse_quests, Quest};
+use crate::secret::SecretString;
 
 /// Discord Gateway opcodes
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -38,69 +38,16 @@ struct HelloData {
     heartbeat_interval: u64,
 }
 
-/// Quest from READY payload
-#[derive(Debug, Deserialize)]
-#[allow(dead_code)]
-struct ReadyQuest {
-    id: String,
-    config: ReadyQuestConfig,
-    #[serde(default)]
-    user_status: Option<ReadyQuestUserStatus>,
-}
-
-#[derive(Debug, Deserialize)]
-#[allow(dead_code)]
-struct ReadyQuestConfig {
-    #[serde(default)]
-    expires_at: Option<String>,
-    #[serde(default)]
-    messages: Option<ReadyQuestMessages>,
-    #[serde(default)]
-    application: Option<ReadyQuestApplication>,
-    #[serde(default, rename = "taskConfig")]
-    task_config: Option<Value>,
-    #[serde(default, rename = "taskConfigV2")]
-    task_config_v2: Option<Value>,
-}
-
-#[derive(Debug, Deserialize)]
-#[allow(dead_code)]
-struct ReadyQuestMessages {
-    #[serde(default, rename = "questName")]
-    quest_name: Option<String>,
-}
-
-#[derive(Debug, Deserialize)]
-#[allow(dead_code)]
-struct ReadyQuestApplication {
-    #[serde(default)]
-    id: Option<String>,
-    #[serde(default)]
-    name: Option<String>,
-    #[serde(default)]
-    icon: Option<String>,
-}
-
-#[derive(Debug, Deserialize)]
 #[allow(dead_code)]
-struct ReadyQuestUserStatus {
-    #[serde(default, rename = "enrolledAt")]
-    enrolled_at: Option<String>,
-    #[serde(default, rename = "completedAt")]
-    completed_at: Option<String>,
-    #[serde(default)]
-    progress: Option<Value>,
-}
+#[instrument(name = "gateway", skip_all)]
+pub async fn get_quests_from_gateway(token: &SecretString, endpoints: &DiscordEndpoints) -> Result<Vec<Quest>> {
+    // Use non-compressed JSON mode for simplicity
+    let gateway_url = endpoints.gateway_connect_url();
 
-#[allow(dead_code)]
-pub async fn get_quests_from_gateway(token: &str) -> Result<Vec<Quest>> {
-    println!("Connecting to Discord Gateway...");
+    info!("Connecting to Discord Gateway: {}", gateway_url);
 
-    // Use non-compressed JSON mode for simplicity
-    let gateway_url = "wss://gateway.discord.gg/?v=9&encoding=json";
-    
     // Connect to Gateway
-    let (ws_stream, _) = connect_async(gateway_url)
+    let (ws_stream, _) = connect_async(gateway_url.as_str())
         .await
         .context("Could not connect to Discord Gateway")?;
 
@@ -117,18 +64,18 @@ pub async fn get_quests_from_gateway(token: &str) -> Result<Vec<Quest>> {
                 let text: String = utf8_text.to_string();
                 // Parse JSON directly
                 if let Ok(payload) = serde_json::from_str::<GatewayPayload>(&text) {
-                    println!("Received Gateway message: op={}, t={:?}", payload.op, payload.t);
+                    debug!("Received Gateway message: op={}, t={:?}", payload.op, payload.t);
                     
                     match payload.op {
                         10 => {
                             // HELLO
-                            println!("Received HELLO event");
+                            debug!("Received HELLO event");
 
                             // Send Identify with better client properties
                             let identify = json!({
                                 "op": 2,
                                 "d": {
-                                    "token": token,
+                                    "token": token.expose_secret(),
                                     "capabilities": 30717,
                                     "properties": {
                                         "os": "Windows",
@@ -170,38 +117,31 @@ pub async fn get_quests_from_gateway(token: &str) -> Result<Vec<Quest>> {
                                 .await
                                 .context("Failed to send Identify")?;
 
-                            println!("Identify sent");
+                            debug!("Identify sent");
                         }
                         0 => {
                             // DISPATCH
                             if let Some(event_type) = &payload.t {
-                                println!("Received DISPATCH event: {}", event_type);
+                                debug!("Received DISPATCH event: {}", event_type);
                                 
                                 // Check for quests in various events
                                 if let Some(d) = &payload.d {
                                     // Debug: print available keys for key events
                                     if event_type == "READY" || event_type == "READY_SUPPLEMENTAL" {
                                         if let Some(obj) = d.as_object() {
-                                            println!("{} payload keys: {:?}", event_type, obj.keys().collect::<Vec<_>>());
+                                            debug!("{} payload keys: {:?}", event_type, obj.keys().collect::<Vec<_>>());
                                         }
                                     }
                                     
                                     // Try to find quests in any event
                                     if let Some(quest_array) = d.get("quests") {
-                                        println!("Found quests field in {} with {} items", 
+                                        info!("Found quests field in {} with {} items", 
                                             event_type,
                                             quest_array.as_array().map(|a| a.len()).unwrap_or(0));
                                         
-                                        if let Ok(ready_quests) =
-                                            serde_json::from_value::<Vec<ReadyQuest>>(
-                                                quest_array.clone(),
-                                            )
-                                        {
-                                            quests = ready_quests
-                                                .into_iter()
-                                                .map(convert_ready_quest_to_quest)
-                                                .collect();
-                                            println!(
+                                        if let Some(values) = quest_array.as_array() {
+                                            quests = parse_quests(values);
+                                            info!(
                                                 "Successfully parsed {} quests",
                                                 quests.len()
                                             );
@@ -215,7 +155,7 @@ pub async fn get_quests_from_gateway(token: &str) -> Result<Vec<Quest>> {
                                 
                                 // After READY_SUPPLEMENTAL, if still no quests, return empty
                                 if event_type == "READY_SUPPLEMENTAL" {
-                                    println!("No quests in READY_SUPPLEMENTAL either, returning empty list");
+                                    info!("No quests in READY_SUPPLEMENTAL either, returning empty list");
                                     let _ = write.close().await;
                                     return Ok(quests);
                                 }
@@ -223,34 +163,34 @@ pub async fn get_quests_from_gateway(token: &str) -> Result<Vec<Quest>> {
                         }
                         11 => {
                             // HEARTBEAT_ACK
-                            println!("Received heartbeat ack");
+                            debug!("Received heartbeat ack");
                         }
                         1 => {
                             // HEARTBEAT request from server
-                            println!("Server requested heartbeat, sending...");
+                            debug!("Server requested heartbeat, sending...");
                             let heartbeat = json!({"op": 1, "d": null});
                             let _ = write.send(Message::Text(heartbeat.to_string().into())).await;
                         }
                         9 => {
                             // Invalid Session
-                            println!("Invalid session (op=9)");
+                            warn!("Invalid session (op=9)");
                             break;
                         }
                         7 => {
                             // Reconnect
-                            println!("Server requested reconnect (op=7)");
+                            warn!("Server requested reconnect (op=7)");
                             break;
                         }
                         _ => {
-                            println!("Received unknown opcode: {}", payload.op);
+                            debug!("Received unknown opcode: {}", payload.op);
                         }
                     }
                 } else {
-                    println!("Could not parse JSON: {}", &text[..text.len().min(200)]);
+                    warn!("Could not parse JSON: {}", &text[..text.len().min(200)]);
                 }
             }
             Message::Close(frame) => {
-                println!("Gateway connection closed: {:?}", frame);
+                info!("Gateway connection closed: {:?}", frame);
                 break;
             }
             _ => {}
@@ -260,68 +200,47 @@ pub async fn get_quests_from_gateway(token: &str) -> Result<Vec<Quest>> {
     Ok(quests)
 }
 
-#[allow(dead_code)]
-fn convert_ready_quest_to_quest(rq: ReadyQuest) -> Quest {
-    let config = &rq.config;
-    let messages = config.messages.as_ref();
-    let application = config.application.as_ref();
-    let user_status = rq.user_status.as_ref();
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::mock_endpoints;
+    use mock_discord::{GatewayOptions, MockDiscord, QuestFixture, MOCK_TOKEN};
 
-    // Calculate progress from user_status
-    let progress = user_status
-        .and_then(|us| us.progress.as_ref())
-        .and_then(|p| {
-            // Try to extract progress value
-            if let Some(obj) = p.as_object() {
-                for (_, v) in obj {
-                    if let Some(val) = v.get("value").and_then(|v| v.as_f64()) {
-                        return Some(val);
-                    }
-                }
-            }
-            None
-        })
-        .unwrap_or(0.0);
 
-    // Get seconds needed from task config
-    let task_config = config
-        .task_config_v2
-        .as_ref()
-        .or(config.task_config.as_ref());
+    #[tokio::test]
+    async fn test_quests_from_ready() {
+        let mock = MockDiscord::start().await;
+        mock.add_quest(QuestFixture::new("1", "WATCH_VIDEO", 900).enrolled().with_progress(450.0));
 
-    let (seconds_needed, task_type) = task_config
-        .and_then(|tc| tc.get("tasks"))
-        .and_then(|tasks| tasks.as_object())
-        .map(|tasks| {
-            for (task_name, task_data) in tasks {
-                if let Some(target) = task_data.get("target").and_then(|t| t.as_u64()) {
-                    return (target as u32, task_name.clone());
-                }
-            }
-            (0u32, String::new())
-        })
-        .unwrap_or((0, String::new()));
+        let quests = get_quests_from_gateway(&MOCK_TOKEN.into(), &mock_endpoints(&mock)).await.unwrap();
+
+        assert_eq!(quests.len(), 1);
+        assert_eq!(quests[0].tasks()["WATCH_VIDEO"].target, 900);
+        assert_eq!(quests[0].task_percent("WATCH_VIDEO"), 50.0);
+        assert!(quests[0].is_enrolled());
+    }
+
+    #[tokio::test]
+    async fn test_quests_from_ready_supplemental() {
+        let mock = MockDiscord::start().await;
+        mock.add_quest(QuestFixture::new("1", "PLAY_ON_DESKTOP", 900));
+        mock.set_gateway_options(GatewayOptions {
+            quests_in_ready: false,
+            ..Default::default()
+        });
+
+        let quests = get_quests_from_gateway(&MOCK_TOKEN.into(), &mock_endpoints(&mock)).await.unwrap();
+
+        assert_eq!(quests.len(), 1);
+        assert!(!quests[0].is_enrolled());
+    }
+
+    #[tokio::test]
+    async fn test_bad_token_closes_session() {
+        let mock = MockDiscord::start().await;
+        mock.add_quest(QuestFixture::new("1", "WATCH_VIDEO", 900));
 
-    Quest {
-        id: rq.id,
-        name: messages
-            .and_then(|m| m.quest_name.clone())
-            .unwrap_or_else(|| "Unknown Quest".to_string()),
-        description: String::new(),
-        progress: progress / seconds_needed as f64 * 100.0,
-        seconds_needed,
-        task_type,
-        application_id: application
-            .and_then(|a| a.id.clone())
-            .unwrap_or_default(),
-        application_name: application
-            .and_then(|a| a.name.clone())
-            .unwrap_or_default(),
-        application_icon: application.and_then(|a| a.icon.clone()),
-        expires_at: config.expires_at.clone(),
-        enrolled: user_status.and_then(|us| us.enrolled_at.clone()).is_some(),
-        completed: user_status
-            .and_then(|us| us.completed_at.clone())
-            .is_some(),
+        let quests = get_quests_from_gateway(&"wrong-token".into(), &mock_endpoints(&mock)).await.unwrap();
+        assert!(quests.is_empty());
     }
 }
@@ -0,0 +1,340 @@
+use crate::discord_api::DiscordApiClient;
+use crate::jobs::{JobTask, QuestJob};
+use crate::models::Quest;
+use crate::quest_completer::{self, QuestControl};
+use crate::quest_events::{OutcomeEvents, QuestEvents};
+use crate::verify;
+use anyhow::{Context, Result};
+use tokio::sync::mpsc::Receiver;
+use tracing::{info, instrument};
+
+/// User settings applied when a quest is started from its id alone
+#[derive(Debug, Clone)]
+pub struct RunOptions {
+    /// Video seconds advanced per real second
+    pub speed_multiplier: f64,
+    /// Seconds between video progress updates
+    pub heartbeat_interval: u64,
+    /// Stream key for stream quests (random if `None`)
+    pub stream_key: Option<String>,
+}
+
+impl Default for RunOptions {
+    fn default() -> Self {
+        Self {
+            speed_multiplier: 1.0,
+            heartbeat_interval: 3,
+            stream_key: None,
+        }
+    }
+}
+
+/// What [`prepare_quest`] decided to do with a quest
+#[derive(Debug, Clone, PartialEq)]
+pub enum Prepared {
+    /// Nothing left to do
+    AlreadyCompleted,
+    /// Ready to hand to [`run_job`]
+    Run(QuestJob),
+}
+
+/// Task names we can complete, in order of preference when a quest offers several
+///
+/// Video is the fastest, play-on-desktop heartbeats need nothing running
+/// locally, and streaming is the fallback.
+const SUPPORTED_TASKS: &[(&str, &str)] = &[
+    ("WATCH_VIDEO", "video"),
+    ("WATCH_VIDEO_ON_MOBILE", "video"),
+    ("PLAY_ON_DESKTOP", "game"),
+    ("STREAM_ON_DESKTOP", "stream"),
+];
+
+/// Pick the task to complete, returning its name and kind
+pub fn pick_task(quest: &Quest) -> Option<(&'static str, &'static str)> {
+    let tasks = quest.tasks();
+    SUPPORTED_TASKS
+        .iter()
+        .find(|(name, _)| tasks.contains_key(*name))
+        .copied()
+}
+
+/// Turn a quest into a job, given the settings to use
+fn plan(quest: &Quest, options: &RunOptions) -> Result<Prepared> {
+    if quest.is_completed() {
+        return Ok(Prepared::AlreadyCompleted);
+    }
+
+    let (task_name, kind) = pick_task(quest).with_context(|| {
+        let tasks: Vec<&str> = quest.tasks().keys().map(|k| k.as_str()).collect();
+        format!("Quest {} has no supported task (tasks: {})", quest.id, tasks.join(", "))
+    })?;
+    let target = quest.tasks()[task_name].target;
+    let progress = quest.task_percent(task_name);
+
+    let task = match kind {
+        "video" => JobTask::Video {
+            speed_multiplier: options.speed_multiplier,
+            heartbeat_interval: options.heartbeat_interval,
+        },
+        "stream" => JobTask::Stream {
+            stream_key: options
+                .stream_key
+                .clone()
+                .unwrap_or_else(quest_completer::generate_stream_key),
+        },
+        _ => JobTask::Game {
+            application_id: quest
+                .application_id()
+                .with_context(|| format!("Quest {} has no application id", quest.id))?
+                .to_string(),
+        },
+    };
+
+    Ok(Prepared::Run(QuestJob::new(quest.id.clone(), task, target, progress)))
+}
+
+/// Refetch a quest, enroll in it if needed and work out how to complete it
+#[instrument(name = "prepare", skip_all, fields(quest_id = quest_id))]
+pub async fn prepare_quest(client: &DiscordApiClient, quest_id: &str, options: &RunOptions) -> Result<Prepared> {
+    let mut quest = client
+        .get_quests()
+        .await?
+        .into_iter()
+        .find(|q| q.id == quest_id)
+        .with_context(|| format!("Quest {} not found", quest_id))?;
+
+    if !quest.is_enrolled() {
+        info!("Quest {} is not enrolled, enrolling first", quest_id);
+        let status = client.accept_quest(quest_id).await?;
+        quest.user_status = serde_json::from_value(status).ok();
+    }
+
+    plan(&quest, options)
+}
+
+/// Run a job with the completer matching its task
+///
+/// Emits `started` with the job before anything else. Once the completer
+/// reports the quest done, the completion is checked against a fresh copy of
+/// the quest (see [`verify::verify_completion`]). Everything the run logs is
+/// inside a `quest` span with the quest id and task type.
+#[instrument(name = "quest", skip_all, fields(quest_id = %job.quest_id, kind = job.task.kind()))]
+pub async fn run_job<E: QuestEvents + ?Sized>(
+    client: &DiscordApiClient,
+    job: &QuestJob,
+    events: &E,
+    mut control_rx: Receiver<QuestControl>,
+) -> Result<()> {
+    events.started(job);
+    let tracker = OutcomeEvents::new(events);
+    run_completer(client, job, &tracker, &mut control_rx).await?;
+
+    if tracker.reached_target() {
+        match verify::verify_completion(client, &job.quest_id, events, &mut control_rx).await {
+            Some(report) if report.verified => events.verified(&report),
+            Some(report) => events.verification_failed(&report),
+            None => {}
+        }
+    }
+    Ok(())
+}
+
+async fn run_completer<E: QuestEvents + ?Sized>(
+    client: &DiscordApiClient,
+    job: &QuestJob,
+    events: &E,
+    control_rx: &mut Receiver<QuestControl>,
+) -> Result<()> {
+    match &job.task {
+        JobTask::Video { speed_multiplier, heartbeat_interval } => {
+            quest_completer::complete_video_quest(
+                client,
+                job.quest_id.clone(),
+                job.seconds_needed,
+                job.progress,
+                *speed_multiplier,
+                *heartbeat_interval,
+                events,
+                control_rx,
+            )
+            .await
+        }
+        JobTask::Stream { stream_key } => {
+            quest_completer::complete_stream_quest(
+                client,
+                job.quest_id.clone(),
+                stream_key.clone(),
+                job.seconds_needed,
+                job.progress,
+                events,
+                control_rx,
+            )
+            .await
+        }
+        JobTask::Game { application_id } => {
+            quest_completer::complete_game_quest_via_heartbeat(
+                client,
+                job.quest_id.clone(),
+                application_id.clone(),
+                job.seconds_needed,
+                job.progress,
+                events,
+                control_rx,
+            )
+            .await
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::quest_events::{QuestEvent, RecordingEvents};
+    use crate::test_support::mock_client;
+    use crate::verify::VERIFY_ATTEMPTS;
+    use mock_discord::{MockDiscord, QuestFixture, Route, ScriptedResponse};
+    use serde_json::json;
+
+    fn video_job() -> QuestJob {
+        let task = JobTask::Video { speed_multiplier: 1.0, heartbeat_interval: 10 };
+        QuestJob::new("1".into(), task, 30, 0.0)
+    }
+
+    #[test]
+    fn test_prefers_video_over_other_tasks() {
+        let quest = json!({
+            "id": "1",
+            "config": {
+                "application": { "id": "42" },
+                "task_config_v2": { "tasks": {
+                    "PLAY_ON_XBOX": { "target": 600 },
+                    "PLAY_ON_DESKTOP": { "target": 900 },
+                    "WATCH_VIDEO": { "target": 30 },
+                }},
+            },
+            "user_status": {
+                "enrolled_at": "2025-01-01T00:00:00Z",
+                "completed_at": null,
+                "progress": { "WATCH_VIDEO": { "value": 15 }, "PLAY_ON_DESKTOP": { "value": 800 } },
+            },
+        });
+
+        let quest: Quest = serde_json::from_value(quest).unwrap();
+        let Prepared::Run(job) = plan(&quest, &RunOptions::default()).unwrap() else {
+            panic!("expected a job");
+        };
+        assert_eq!(job.task.kind(), "video");
+        assert_eq!(job.seconds_needed, 30);
+        assert_eq!(job.progress, 50.0);
+    }
+
+    #[test]
+    fn test_unsupported_tasks_are_rejected() {
+        let quest = json!({
+            "id": "1",
+            "config": { "task_config_v2": { "tasks": { "PLAY_ON_XBOX": { "target": 600 } } } },
+            "user_status": null,
+        });
+        let quest: Quest = serde_json::from_value(quest).unwrap();
+        let err = plan(&quest, &RunOptions::default()).unwrap_err();
+        assert!(err.to_string().contains("PLAY_ON_XBOX"));
+    }
+
+    #[tokio::test]
+    async fn test_prepare_enrolls_first() {
+        let mock = MockDiscord::start().await;
+        mock.add_quest(QuestFixture::new("1", "PLAY_ON_DESKTOP", 900).with_application("42", "Mock Game"));
+        mock.add_quest(QuestFixture::new("2", "WATCH_VIDEO", 30).enrolled().with_progress(30.0));
+        let client = mock_client(&mock);
+
+        let prepared = prepare_quest(&client, "1", &RunOptions::default()).await.unwrap();
+        let Prepared::Run(job) = prepared else {
+            panic!("expected a job");
+        };
+        assert_eq!(job.task, JobTask::Game { application_id: "42".into() });
+        assert_eq!(mock.requests_to(Route::Enroll).len(), 1);
+        assert!(mock.quest("1").unwrap().enrolled);
+
+        assert!(prepare_quest(&client, "3", &RunOptions::default()).await.is_err());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_completion_is_verified() {
+        let mock = MockDiscord::start().await;
+        mock.add_quest(QuestFixture::new("1", "WATCH_VIDEO", 30).enrolled());
+        let client = mock_client(&mock);
+        let events = RecordingEvents::new();
+        let (_control_tx, control_rx) = tokio::sync::mpsc::channel(1);
+
+        run_job(&client, &video_job(), &events, control_rx).await.unwrap();
+
+        let Some(QuestEvent::Verified(report)) = events.events().last().cloned() else {
+            panic!("expected a verified event");
+        };
+        assert_eq!(report.attempts, 1);
+        assert_eq!(report.progress, Some(100.0));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_unrecorded_completion_fails_verification() {
+        let mock = MockDiscord::start().await;
+        mock.add_quest(QuestFixture::new("1", "WATCH_VIDEO", 30).enrolled());
+        // The progress endpoint claims completion that the quest list never shows
+        mock.script(
+            Route::VideoProgress,
+            ScriptedResponse::Json(200, json!({
+                "enrolled_at": "2025-01-01T00:00:00+00:00",
+                "completed_at": "2025-01-01T00:10:00+00:00",
+                "progress": { "WATCH_VIDEO": { "value": 30.0 } },
+            })),
+        );
+        let client = mock_client(&mock);
+        let events = RecordingEvents::new();
+        let (_control_tx, control_rx) = tokio::sync::mpsc::channel(1);
+
+        run_job(&client, &video_job(), &events, control_rx).await.unwrap();
+
+        let recorded = events.events();
+        assert!(recorded.contains(&QuestEvent::Completed));
+        let Some(QuestEvent::VerificationFailed(report)) = recorded.last().cloned() else {
+            panic!("expected a failed verification");
+        };
+        assert_eq!(report.attempts, VERIFY_ATTEMPTS);
+        assert_eq!(report.completed_at, None);
+        assert_eq!(report.progress, Some(0.0));
+        assert_eq!(mock.requests_to(Route::Quests).len(), VERIFY_ATTEMPTS as usize);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_stop_cancels_verification() {
+        let mock = MockDiscord::start().await;
+        mock.add_quest(QuestFixture::new("1", "WATCH_VIDEO", 30).enrolled());
+        // Completion is claimed on the first step but never shows up, so verification backs off
+        mock.script(
+            Route::VideoProgress,
+            ScriptedResponse::Json(200, json!({
+                "enrolled_at": "2025-01-01T00:00:00+00:00",
+                "completed_at": "2025-01-01T00:10:00+00:00",
+                "progress": { "WATCH_VIDEO": { "value": 30.0 } },
+            })),
+        );
+        let client = mock_client(&mock);
+        let events = RecordingEvents::new();
+        let (control_tx, control_rx) = tokio::sync::mpsc::channel(1);
+
+        let job = video_job();
+        let run = run_job(&client, &job, &events, control_rx);
+        let stop = async {
+            // Stop while verification waits to refetch
+            while mock.requests_to(Route::Quests).is_empty() {
+                tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+            }
+            control_tx.send(QuestControl::Stop).await.unwrap();
+        };
+        let (result, _) = tokio::join!(run, stop);
+        result.unwrap();
+
+        assert!(mock.requests_to(Route::Quests).len() < VERIFY_ATTEMPTS as usize);
+        assert_eq!(events.events().last(), Some(&QuestEvent::Completed));
+    }
+}
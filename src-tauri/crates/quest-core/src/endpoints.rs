@@ -0,0 +1,147 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use tracing::info;
+
+/// Production REST base (without the version segment)
+pub const DEFAULT_API_BASE: &str = "https://discord.com/api";
+/// API version used for both REST and gateway
+pub const DEFAULT_API_VERSION: u8 = 9;
+/// Production gateway host
+pub const DEFAULT_GATEWAY_URL: &str = "wss://gateway.discord.gg";
+
+/// File name looked up in the app config directory
+pub const ENDPOINTS_FILE_NAME: &str = "endpoints.json";
+
+/// Environment variables that override the file/default values
+pub const ENV_API_BASE: &str = "DQH_API_BASE";
+pub const ENV_API_VERSION: &str = "DQH_API_VERSION";
+pub const ENV_GATEWAY_URL: &str = "DQH_GATEWAY_URL";
+/// Points at an explicit endpoints file instead of the app config one
+pub const ENV_ENDPOINTS_FILE: &str = "DQH_ENDPOINTS_FILE";
+
+/// Discord endpoint configuration
+///
+/// Every URL the backend talks to is derived from this, so the whole app can be
+/// pointed at a local stand-in server instead of discord.com.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DiscordEndpoints {
+    /// REST base, e.g. `https://discord.com/api`
+    pub api_base: String,
+    /// API version, appended to the REST base as `/v{version}`
+    pub api_version: u8,
+    /// Gateway websocket URL, e.g. `wss://gateway.discord.gg`
+    pub gateway_url: String,
+}
+
+impl Default for DiscordEndpoints {
+    fn default() -> Self {
+        Self {
+            api_base: DEFAULT_API_BASE.to_string(),
+            api_version: DEFAULT_API_VERSION,
+            gateway_url: DEFAULT_GATEWAY_URL.to_string(),
+        }
+    }
+}
+
+impl DiscordEndpoints {
+    /// Endpoints for a local server, e.g. `http://127.0.0.1:8080`
+    pub fn local(rest_base: &str, gateway_url: &str) -> Self {
+        Self {
+            api_base: rest_base.trim_end_matches('/').to_string(),
+            api_version: DEFAULT_API_VERSION,
+            gateway_url: gateway_url.to_string(),
+        }
+    }
+
+    /// Load configuration: defaults, then the config file (if any), then env overrides
+    ///
+    /// `config_dir` is the app config directory; `DQH_ENDPOINTS_FILE` takes precedence over it.
+    pub fn load(config_dir: Option<&Path>) -> Result<Self> {
+        let file = std::env::var_os(ENV_ENDPOINTS_FILE)
+            .map(std::path::PathBuf::from)
+            .or_else(|| config_dir.map(|dir| dir.join(ENDPOINTS_FILE_NAME)));
+
+        let mut endpoints = match file {
+            Some(path) if path.exists() => Self::from_file(&path)?,
+            _ => Self::default(),
+        };
+
+        endpoints.apply_env()?;
+        Ok(endpoints)
+    }
+
+    /// Read configuration from a JSON file; missing fields fall back to the defaults
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Could not read endpoints file: {:?}", path))?;
+        let endpoints: Self = serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse endpoints file: {:?}", path))?;
+
+        info!("Loaded Discord endpoints from {:?}", path);
+        Ok(endpoints)
+    }
+
+    /// Apply `DQH_API_BASE`, `DQH_API_VERSION` and `DQH_GATEWAY_URL` overrides
+    pub fn apply_env(&mut self) -> Result<()> {
+        if let Ok(base) = std::env::var(ENV_API_BASE) {
+            self.api_base = base.trim_end_matches('/').to_string();
+        }
+        if let Ok(version) = std::env::var(ENV_API_VERSION) {
+            self.api_version = version
+                .parse()
+                .with_context(|| format!("Invalid {}: {}", ENV_API_VERSION, version))?;
+        }
+        if let Ok(gateway) = std::env::var(ENV_GATEWAY_URL) {
+            self.gateway_url = gateway;
+        }
+        Ok(())
+    }
+
+    /// Full REST URL for a path such as `/users/@me`
+    pub fn rest_url(&self, path: &str) -> String {
+        format!(
+            "{}/v{}{}",
+            self.api_base.trim_end_matches('/'),
+            self.api_version,
+            path
+        )
+    }
+
+    /// Gateway URL with version and (non-compressed) JSON encoding query
+    pub fn gateway_connect_url(&self) -> String {
+        format!(
+            "{}/?v={}&encoding=json",
+            self.gateway_url.trim_end_matches('/'),
+            self.api_version
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_urls() {
+        let endpoints = DiscordEndpoints::default();
+        assert_eq!(endpoints.rest_url("/users/@me"), "https://discord.com/api/v9/users/@me");
+        assert_eq!(
+            endpoints.gateway_connect_url(),
+            "wss://gateway.discord.gg/?v=9&encoding=json"
+        );
+    }
+
+    #[test]
+    fn test_partial_file_uses_defaults() {
+        let endpoints: DiscordEndpoints =
+            serde_json::from_str(r#"{ "api_base": "http://127.0.0.1:8080/api/" }"#).unwrap();
+        assert_eq!(endpoints.api_version, DEFAULT_API_VERSION);
+        assert_eq!(endpoints.gateway_url, DEFAULT_GATEWAY_URL);
+        assert_eq!(
+            endpoints.rest_url("/quests/@me"),
+            "http://127.0.0.1:8080/api/v9/quests/@me"
+        );
+    }
+}
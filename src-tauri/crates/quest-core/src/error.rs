@@ -0,0 +1,161 @@
+use crate::secret::redact_tokens;
+use reqwest::StatusCode;
+use serde::Serialize;
+use std::fmt;
+
+/// Errors produced by [`crate::discord_api::DiscordApiClient`]
+///
+/// Serialized with a stable `kind` tag (e.g. `{"kind": "rate_limited", ...}`)
+/// so the frontend can react to the failure type instead of parsing strings.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum DiscordApiError {
+    /// 401, or a token that can't be sent as a header
+    Unauthorized { message: String },
+    /// 429; `retry_after` is in seconds
+    RateLimited { retry_after: f64, global: bool, message: String },
+    /// 404
+    NotFound { message: String },
+    /// 403
+    Forbidden { message: String },
+    /// Any other non-success status, with Discord's JSON error code if present
+    DiscordError { status: u16, code: Option<u64>, message: String },
+    /// Request never got a response (DNS, TLS, connection reset, ...)
+    Transport { message: String },
+    /// Response body could not be parsed
+    Decode { message: String },
+}
+
+pub type ApiResult<T> = std::result::Result<T, DiscordApiError>;
+
+impl fmt::Display for DiscordApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DiscordApiError::Unauthorized { message } => write!(f, "Unauthorized: {}", message),
+            DiscordApiError::RateLimited { retry_after, .. } => {
+                write!(f, "Rate limited, retry after {:.1}s", retry_after)
+            }
+            DiscordApiError::NotFound { message } => write!(f, "Not found: {}", message),
+            DiscordApiError::Forbidden { message } => write!(f, "Forbidden: {}", message),
+            DiscordApiError::DiscordError { status, code: Some(code), message } => {
+                write!(f, "Discord error {} (code {}): {}", status, code, message)
+            }
+            DiscordApiError::DiscordError { status, code: None, message } => {
+                write!(f, "Discord error {}: {}", status, message)
+            }
+            DiscordApiError::Transport { message } => write!(f, "Network error: {}", message),
+            DiscordApiError::Decode { message } => write!(f, "Invalid response: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for DiscordApiError {}
+
+impl DiscordApiError {
+    /// Stable identifier of the variant, same as the serialized `kind`
+    pub fn kind(&self) -> &'static str {
+        match self {
+            DiscordApiError::Unauthorized { .. } => "unauthorized",
+            DiscordApiError::RateLimited { .. } => "rate_limited",
+            DiscordApiError::NotFound { .. } => "not_found",
+            DiscordApiError::Forbidden { .. } => "forbidden",
+            DiscordApiError::DiscordError { .. } => "discord_error",
+            DiscordApiError::Transport { .. } => "transport",
+            DiscordApiError::Decode { .. } => "decode",
+        }
+    }
+
+    /// Whether the failure is likely transient (network, rate limit, 5xx, garbled body)
+    ///
+    /// Auth, permission and other 4xx errors won't go away by trying again.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            DiscordApiError::Transport { .. }
+            | DiscordApiError::RateLimited { .. }
+            | DiscordApiError::Decode { .. } => true,
+            DiscordApiError::DiscordError { status, .. } => *status >= 500 || *status == 408,
+            DiscordApiError::Unauthorized { .. }
+            | DiscordApiError::NotFound { .. }
+            | DiscordApiError::Forbidden { .. } => false,
+        }
+    }
+
+    pub(crate) fn transport(action: &str, error: reqwest::Error) -> Self {
+        DiscordApiError::Transport {
+            message: redact_tokens(&format!("{}: {}", action, error)).into_owned(),
+        }
+    }
+
+    pub(crate) fn decode(action: &str, error: impl fmt::Display) -> Self {
+        DiscordApiError::Decode {
+            message: redact_tokens(&format!("{}: {}", action, error)).into_owned(),
+        }
+    }
+
+    /// Classify a non-success response from its status, headers and JSON body
+    ///
+    /// Token-shaped strings in the body are masked, in case the request was echoed back.
+    pub(crate) fn from_status(
+        status: StatusCode,
+        retry_after_header: Option<f64>,
+        body: &str,
+    ) -> Self {
+        let json: serde_json::Value = serde_json::from_str(body).unwrap_or_default();
+        let message = json
+            .get("message")
+            .and_then(|m| m.as_str())
+            .map(|m| m.to_string())
+            .unwrap_or_else(|| {
+                if body.is_empty() {
+                    status.to_string()
+                } else {
+                    body.chars().take(200).collect()
+                }
+            });
+        let message = redact_tokens(&message).into_owned();
+
+        match status {
+            StatusCode::UNAUTHORIZED => DiscordApiError::Unauthorized { message },
+            StatusCode::FORBIDDEN => DiscordApiError::Forbidden { message },
+            StatusCode::NOT_FOUND => DiscordApiError::NotFound { message },
+            StatusCode::TOO_MANY_REQUESTS => DiscordApiError::RateLimited {
+                // The body value is more precise than the whole-second header
+                retry_after: json
+                    .get("retry_after")
+                    .and_then(|r| r.as_f64())
+                    .or(retry_after_header)
+                    .filter(|r| r.is_finite())
+                    .unwrap_or(1.0),
+                global: json.get("global").and_then(|g| g.as_bool()).unwrap_or(false),
+                message,
+            },
+            _ => DiscordApiError::DiscordError {
+                status: status.as_u16(),
+                code: json.get("code").and_then(|c| c.as_u64()),
+                message,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_rate_limit() {
+        let body = r#"{"message": "You are being rate limited.", "retry_after": 2.5, "global": false}"#;
+        let err = DiscordApiError::from_status(StatusCode::TOO_MANY_REQUESTS, Some(3.0), body);
+        assert!(matches!(err, DiscordApiError::RateLimited { retry_after, .. } if retry_after == 2.5));
+    }
+
+    #[test]
+    fn test_serialized_kind() {
+        let err = DiscordApiError::from_status(StatusCode::BAD_REQUEST, None, r#"{"message": "Already completed", "code": 10078}"#);
+        let json = serde_json::to_value(&err).unwrap();
+        assert_eq!(json["kind"], "discord_error");
+        assert_eq!(json["code"], 10078);
+        assert_eq!(json["message"], "Already completed");
+        assert_eq!(err.kind(), "discord_error");
+    }
+}
@@ -0,0 +1,168 @@
+//! Versioned payloads of the quest events sent to the frontend
+//!
+//! Every quest event is a [`QuestEventPayload`]: the same envelope, plus a
+//! `detail` that depends on the event. Bump [`QUEST_EVENT_SCHEMA_VERSION`]
+//! whenever a field is renamed, removed or changes meaning; adding a field
+//! doesn't need a bump.
+//!
+//! | Event                       | `detail`                      |
+//! |-----------------------------|-------------------------------|
+//! | `quest-started`             | `null`                        |
+//! | `quest-progress`            | `null`                        |
+//! | `quest-complete`            | `null`                        |
+//! | `quest-stopped`             | [`StoppedDetail`]             |
+//! | `quest-error`               | [`ErrorDetail`]               |
+//! | `quest-retrying`            | [`RetryNotice`]               |
+//! | `quest-paused`              | `null`                        |
+//! | `quest-resumed`             | `null`                        |
+//! | `quest-verified`            | [`VerificationReport`]        |
+//! | `quest-verification-failed` | [`VerificationReport`]        |
+//! | `quest-session-closed`      | [`SessionClosedDetail`]       |
+//!
+//! The TypeScript side of this schema is in `src/api/tauri.ts`.
+//!
+//! [`RetryNotice`]: crate::retry::RetryNotice
+//! [`VerificationReport`]: crate::verify::VerificationReport
+
+use crate::jobs::{JobTask, QuestJob};
+use serde::Serialize;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+/// Version of the payload layout, sent as `version` in every event
+pub const QUEST_EVENT_SCHEMA_VERSION: u32 = 1;
+
+/// Shared by every quest, so events can be ordered across quests
+static NEXT_SEQ: AtomicU64 = AtomicU64::new(1);
+
+/// Envelope of every quest event
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct QuestEventPayload<T> {
+    /// [`QUEST_EVENT_SCHEMA_VERSION`]
+    pub version: u32,
+    /// Increases by one with every quest event the process emits
+    pub seq: u64,
+    pub quest_id: String,
+    /// `video`, `stream` or `game`
+    pub task_type: String,
+    /// Progress percentage (0-100) as last confirmed by Discord
+    pub progress: f64,
+    pub seconds_done: f64,
+    pub seconds_needed: u32,
+    /// Estimated real seconds until the target is reached
+    pub eta_secs: f64,
+    pub detail: T,
+}
+
+/// `detail` of `quest-stopped`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StoppedDetail {
+    /// Progress Discord confirmed for the position saved on stop, if one was
+    pub flushed_progress: Option<f64>,
+}
+
+/// `detail` of `quest-error`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ErrorDetail {
+    pub message: String,
+}
+
+/// `detail` of `quest-session-closed`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SessionClosedDetail {
+    /// Whether Discord accepted the terminal heartbeat
+    pub acknowledged: bool,
+}
+
+/// What one quest run's events have in common, kept up to date as progress comes in
+pub struct QuestEventContext {
+    quest_id: String,
+    task_type: &'static str,
+    seconds_needed: u32,
+    /// Quest seconds credited per real second
+    rate: f64,
+    progress: Mutex<f64>,
+}
+
+impl QuestEventContext {
+    pub fn new(job: &QuestJob) -> Self {
+        // Videos advance at the speed multiplier; streams and games in real time
+        let rate = match job.task {
+            JobTask::Video { speed_multiplier, .. } if speed_multiplier > 0.0 => speed_multiplier,
+            _ => 1.0,
+        };
+        Self {
+            quest_id: job.quest_id.clone(),
+            task_type: job.task.kind(),
+            seconds_needed: job.seconds_needed,
+            rate,
+            progress: Mutex::new(job.progress),
+        }
+    }
+
+    pub fn quest_id(&self) -> &str {
+        &self.quest_id
+    }
+
+    /// Take in a new progress percentage before building the payload that reports it
+    pub fn record_progress(&self, progress: f64) {
+        *self.progress.lock().unwrap() = progress;
+    }
+
+    /// Wrap `detail` in the envelope, with the next sequence number
+    pub fn payload<T>(&self, detail: T) -> QuestEventPayload<T> {
+        let progress = *self.progress.lock().unwrap();
+        let seconds_needed = self.seconds_needed as f64;
+        let seconds_done = (progress / 100.0 * seconds_needed).min(seconds_needed);
+        QuestEventPayload {
+            version: QUEST_EVENT_SCHEMA_VERSION,
+            seq: NEXT_SEQ.fetch_add(1, Ordering::Relaxed),
+            quest_id: self.quest_id.clone(),
+            task_type: self.task_type.to_string(),
+            progress,
+            seconds_done,
+            seconds_needed: self.seconds_needed,
+            eta_secs: (seconds_needed - seconds_done).max(0.0) / self.rate,
+            detail,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_payload_layout() {
+        let job = QuestJob::new(
+            "1".into(),
+            JobTask::Video { speed_multiplier: 4.0, heartbeat_interval: 3 },
+            600,
+            0.0,
+        );
+        let context = QuestEventContext::new(&job);
+        context.record_progress(25.0);
+
+        let first = context.payload(());
+        let second = context.payload(ErrorDetail { message: "boom".into() });
+        assert!(second.seq > first.seq);
+
+        let mut value = serde_json::to_value(&second).unwrap();
+        value.as_object_mut().unwrap().remove("seq");
+        assert_eq!(
+            value,
+            json!({
+                "version": QUEST_EVENT_SCHEMA_VERSION,
+                "quest_id": "1",
+                "task_type": "video",
+                "progress": 25.0,
+                "seconds_done": 150.0,
+                "seconds_needed": 600,
+                "eta_secs": 112.5,
+                "detail": { "message": "boom" },
+            })
+        );
+        assert_eq!(serde_json::to_value(&first).unwrap()["detail"], json!(null));
+    }
+}
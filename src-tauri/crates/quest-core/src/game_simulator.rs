@@ -1,20 +1,76 @@
 use anyhow::{Context, Result};
+use serde::Serialize;
 use std::fs;
 use std::path::PathBuf;
+#[cfg(any(target_os = "windows", target_os = "macos"))]
 use std::process::Command;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::{debug, info, warn};
+
+/// A simulated game started by [`run_simulated_game`] and not stopped since
+///
+/// The process may have been closed by hand in the meantime; the launcher
+/// detaches it, so there is no handle to check.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SimulatedGame {
+    pub name: String,
+    pub path: String,
+    pub executable_name: String,
+    pub app_id: String,
+    /// Unix timestamp (seconds) of the launch
+    pub started_at: u64,
+}
+
+static LAUNCHED_GAMES: Mutex<Vec<SimulatedGame>> = Mutex::new(Vec::new());
+
+/// Simulated games launched by this process and not stopped since, oldest first
+pub fn launched_games() -> Vec<SimulatedGame> {
+    LAUNCHED_GAMES.lock().unwrap().clone()
+}
+
+/// File name of an executable given as a name or a path with either separator
+fn image_name(exec_name: &str) -> &str {
+    exec_name.rsplit(['/', '\\']).next().unwrap_or(exec_name)
+}
+
+#[cfg_attr(not(any(target_os = "windows", target_os = "macos")), allow(dead_code))]
+fn record_launch(name: &str, path: &str, executable_name: &str, app_id: &str) {
+    let started_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let mut games = LAUNCHED_GAMES.lock().unwrap();
+    games.retain(|g| image_name(&g.executable_name) != image_name(executable_name));
+    games.push(SimulatedGame {
+        name: name.to_string(),
+        path: path.to_string(),
+        executable_name: executable_name.to_string(),
+        app_id: app_id.to_string(),
+        started_at,
+    });
+}
+
+#[cfg_attr(not(any(target_os = "windows", target_os = "macos")), allow(dead_code))]
+fn record_stop(exec_name: &str) {
+    LAUNCHED_GAMES
+        .lock()
+        .unwrap()
+        .retain(|g| image_name(&g.executable_name) != image_name(exec_name));
+}
 
 /// Create a simulated game executable
 ///
 /// Copies the template executable to the specified path with the target game name
 pub fn create_simulated_game(path: &str, executable_name: &str, _app_id: &str) -> Result<()> {
-    println!("create_simulated_game called with path: '{}', exe: '{}'", path, executable_name);
+    debug!("create_simulated_game called with path: '{}', exe: '{}'", path, executable_name);
     
     // Create target directory
     let target_dir = PathBuf::from(path);
-    println!("Target directory: {:?}, exists: {}", target_dir, target_dir.exists());
+    debug!("Target directory: {:?}, exists: {}", target_dir, target_dir.exists());
     
     if !target_dir.exists() {
-        println!("Creating directory: {:?}", target_dir);
+        debug!("Creating directory: {:?}", target_dir);
         fs::create_dir_all(&target_dir).context(format!("Could not create target directory: {:?}", target_dir))?;
     }
 
@@ -32,14 +88,14 @@ pub fn create_simulated_game(path: &str, executable_name: &str, _app_id: &str) -
     // If file exists, try to delete it first
     if target_exe.exists() {
         if let Err(e) = fs::remove_file(&target_exe) {
-            println!("Target file exists and remove failed ({}), trying to kill process...", e);
+            warn!("Target file exists and remove failed ({}), trying to kill process...", e);
             // Process might be running, try to stop it
             let _ = stop_simulated_game(executable_name);
             // Wait for process to release the lock
             std::thread::sleep(std::time::Duration::from_millis(500));
             // Try to delete again
             if let Err(e) = fs::remove_file(&target_exe) {
-               println!("Still cannot remove file: {}", e);
+               warn!("Still cannot remove file: {}", e);
                // Continue to copy, see if it overwrites or fails
             }
         }
@@ -50,18 +106,18 @@ pub fn create_simulated_game(path: &str, executable_name: &str, _app_id: &str) -
     let runner_path = get_runner_exe_path()?;
 
     // Copy file
-    println!("Copying runner from {:?} to {:?}", runner_path, target_exe);
+    debug!("Copying runner from {:?} to {:?}", runner_path, target_exe);
     fs::copy(&runner_path, &target_exe).map_err(|e| {
         anyhow::anyhow!("Could not copy executable from {:?} to {:?}: {}", runner_path, target_exe, e)
     })?;
 
-    println!("Simulated game created: {:?}", target_exe);
+    info!("Simulated game created: {:?}", target_exe);
     Ok(())
 }
 
 /// Run the simulated game
 #[cfg(target_os = "windows")]
-pub fn run_simulated_game(name: &str, path: &str, executable_name: &str, _app_id: &str) -> Result<()> {
+pub fn run_simulated_game(name: &str, path: &str, executable_name: &str, app_id: &str) -> Result<()> {
     let target_exe = PathBuf::from(path).join(executable_name);
 
     if !target_exe.exists() {
@@ -74,12 +130,13 @@ pub fn run_simulated_game(name: &str, path: &str, executable_name: &str, _app_id
         .spawn()
         .context("Could not start simulated game")?;
 
-    println!("Simulated game {} started", name);
+    record_launch(name, path, executable_name, app_id);
+    info!("Simulated game {} started", name);
     Ok(())
 }
 
 #[cfg(target_os = "macos")]
-pub fn run_simulated_game(name: &str, path: &str, executable_name: &str, _app_id: &str) -> Result<()> {
+pub fn run_simulated_game(name: &str, path: &str, executable_name: &str, app_id: &str) -> Result<()> {
     let target_exe = PathBuf::from(path).join(executable_name);
 
     if !target_exe.exists() {
@@ -97,7 +154,8 @@ pub fn run_simulated_game(name: &str, path: &str, executable_name: &str, _app_id
         .spawn()
         .context("Could not start simulated game")?;
 
-    println!("Simulated game {} started", name);
+    record_launch(name, path, executable_name, app_id);
+    info!("Simulated game {} started", name);
     Ok(())
 }
 
@@ -121,7 +179,7 @@ pub fn stop_simulated_game(exec_name: &str) -> Result<()> {
         .last()
         .unwrap_or(exec_name);
 
-    println!("Stopping simulated game: Input='{}' -> Image='{}'", exec_name, file_name);
+    debug!("Stopping simulated game: Input='{}' -> Image='{}'", exec_name, file_name);
 
     // Use taskkill command to terminate process
     let output = Command::new("taskkill")
@@ -134,7 +192,8 @@ pub fn stop_simulated_game(exec_name: &str) -> Result<()> {
         anyhow::bail!("Failed to stop game: {}", stderr);
     }
 
-    println!("Simulated game {} stopped", exec_name);
+    record_stop(exec_name);
+    info!("Simulated game {} stopped", exec_name);
     Ok(())
 }
 
@@ -146,7 +205,7 @@ pub fn stop_simulated_game(exec_name: &str) -> Result<()> {
         .last()
         .unwrap_or(exec_name);
 
-    println!("Stopping simulated game: Input='{}' -> Process='{}'", exec_name, file_name);
+    debug!("Stopping simulated game: Input='{}' -> Process='{}'", exec_name, file_name);
 
     // Use pkill to terminate process by name
     let output = Command::new("pkill")
@@ -160,7 +219,8 @@ pub fn stop_simulated_game(exec_name: &str) -> Result<()> {
         anyhow::bail!("Failed to stop game: {}", stderr);
     }
 
-    println!("Simulated game {} stopped", exec_name);
+    record_stop(exec_name);
+    info!("Simulated game {} stopped", exec_name);
     Ok(())
 }
 
@@ -244,6 +304,18 @@ mod tests {
     use super::*;
     use std::env;
 
+    #[test]
+    fn test_launched_games_are_tracked_until_stopped() {
+        record_launch("Game", "C:/games", "bin/game.exe", "123");
+        record_launch("Game again", "C:/games", "bin/game.exe", "123");
+        assert_eq!(launched_games().iter().filter(|g| g.app_id == "123").count(), 1);
+        assert_eq!(launched_games().iter().find(|g| g.app_id == "123").unwrap().name, "Game again");
+
+        // Stopped by image name, whatever the separator
+        record_stop("C:\\games\\bin\\game.exe");
+        assert!(!launched_games().iter().any(|g| g.app_id == "123"));
+    }
+
     #[test]
     #[ignore] // Requires actual file system operations
     fn test_create_simulated_game() {
@@ -0,0 +1,379 @@
+use crate::quest_events::{QuestEvent, QuestEvents};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tracing::warn;
+
+/// File the job store is kept in, inside the app data dir
+pub const JOBS_FILE_NAME: &str = "jobs.json";
+
+/// How often [`JobStore::run_flusher`] writes out progress updates
+pub const FLUSH_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Completer-specific parameters needed to restart a job
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum JobTask {
+    Video { speed_multiplier: f64, heartbeat_interval: u64 },
+    Stream { stream_key: String },
+    Game { application_id: String },
+}
+
+impl JobTask {
+    /// Same names as [`crate::registry::RunningQuestInfo::kind`]
+    pub fn kind(&self) -> &'static str {
+        match self {
+            JobTask::Video { .. } => "video",
+            JobTask::Stream { .. } => "stream",
+            JobTask::Game { .. } => "game",
+        }
+    }
+}
+
+/// A started quest, kept on disk until it completes or is stopped
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QuestJob {
+    pub quest_id: String,
+    #[serde(flatten)]
+    pub task: JobTask,
+    pub seconds_needed: u32,
+    /// Last progress percentage confirmed by Discord
+    pub progress: f64,
+    /// Unix timestamp (seconds) of the last update
+    pub updated_at: u64,
+    /// In-process run number assigned by [`JobStore::save`], so a replaced run can't touch its successor
+    #[serde(skip)]
+    run: u64,
+}
+
+impl QuestJob {
+    pub fn new(quest_id: String, task: JobTask, seconds_needed: u32, progress: f64) -> Self {
+        Self {
+            quest_id,
+            task,
+            seconds_needed,
+            progress,
+            updated_at: unix_now(),
+            run: 0,
+        }
+    }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Jobs that were started but haven't finished, persisted as JSON
+///
+/// Survives crashes and restarts so a quest can be resumed from its last
+/// confirmed progress instead of the user re-entering it. Write failures are
+/// logged and otherwise ignored; the store is a convenience, not a source of truth.
+///
+/// Adding and removing jobs is written out right away. Progress updates only
+/// mark the store dirty and are written by [`run_flusher`](Self::run_flusher),
+/// so running quests don't each hit the disk on every tick.
+pub struct JobStore {
+    path: Option<PathBuf>,
+    jobs: Mutex<HashMap<String, QuestJob>>,
+    next_run: AtomicU64,
+    /// Set on app exit, see [`close`](Self::close)
+    closing: AtomicBool,
+    /// Progress changed since the last write
+    dirty: AtomicBool,
+    /// Held while writing, so snapshots reach the disk in the order they were taken
+    write: Mutex<()>,
+}
+
+impl JobStore {
+    /// Load `<dir>/jobs.json`; a missing or unreadable file starts an empty store
+    pub fn open(dir: &Path) -> Self {
+        let path = dir.join(JOBS_FILE_NAME);
+        let jobs = match Self::read(&path) {
+            Ok(jobs) => jobs,
+            Err(e) => {
+                warn!("Ignoring job store {}: {:#}", path.display(), e);
+                Vec::new()
+            }
+        };
+
+        Self {
+            path: Some(path),
+            jobs: Mutex::new(jobs.into_iter().map(|j| (j.quest_id.clone(), j)).collect()),
+            next_run: AtomicU64::new(1),
+            closing: AtomicBool::new(false),
+            dirty: AtomicBool::new(false),
+            write: Mutex::new(()),
+        }
+    }
+
+    /// Store that is never written to disk
+    pub fn in_memory() -> Self {
+        Self {
+            path: None,
+            jobs: Mutex::new(HashMap::new()),
+            next_run: AtomicU64::new(1),
+            closing: AtomicBool::new(false),
+            dirty: AtomicBool::new(false),
+            write: Mutex::new(()),
+        }
+    }
+
+    fn read(path: &Path) -> Result<Vec<QuestJob>> {
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+        let contents = fs::read_to_string(path).context("Failed to read job store")?;
+        serde_json::from_str(&contents).context("Failed to parse job store")
+    }
+
+    /// Write the whole store (via a temp file, so a crash mid-write keeps the old copy)
+    ///
+    /// The jobs lock is only held to serialize them, not for the disk I/O.
+    fn flush(&self) {
+        let Some(path) = &self.path else {
+            return;
+        };
+
+        let _write = self.write.lock().unwrap();
+        let contents = {
+            let jobs = self.jobs.lock().unwrap();
+            self.dirty.store(false, Ordering::Relaxed);
+            let mut list: Vec<_> = jobs.values().collect();
+            list.sort_by(|a, b| a.quest_id.cmp(&b.quest_id));
+            serde_json::to_vec_pretty(&list)
+        };
+
+        let result = (|| -> Result<()> {
+            let contents = contents?;
+            if let Some(dir) = path.parent() {
+                fs::create_dir_all(dir)?;
+            }
+            let tmp = path.with_extension("json.tmp");
+            fs::write(&tmp, contents)?;
+            fs::rename(&tmp, path)?;
+            Ok(())
+        })();
+
+        if let Err(e) = result {
+            warn!("Failed to save job store {}: {}", path.display(), e);
+        }
+    }
+
+    /// Insert or replace the job for its quest; returns the run number for later updates
+    pub fn save(&self, mut job: QuestJob) -> u64 {
+        let run = self.next_run.fetch_add(1, Ordering::Relaxed);
+        job.run = run;
+        self.jobs.lock().unwrap().insert(job.quest_id.clone(), job);
+        self.flush();
+        run
+    }
+
+    /// Record newly confirmed progress of run `run`
+    ///
+    /// Written by the next [`flush_if_dirty`](Self::flush_if_dirty), or right
+    /// away once the store is closing.
+    pub fn update_progress(&self, quest_id: &str, run: u64, progress: f64) {
+        {
+            let mut jobs = self.jobs.lock().unwrap();
+            let Some(job) = jobs.get_mut(quest_id).filter(|j| j.run == run) else {
+                return;
+            };
+            job.progress = progress;
+            job.updated_at = unix_now();
+            self.dirty.store(true, Ordering::Relaxed);
+        }
+        if self.is_closing() {
+            self.flush();
+        }
+    }
+
+    /// Drop the job once run `run` is over; a newer run of the same quest is left alone
+    pub fn finish(&self, quest_id: &str, run: u64) {
+        let removed = {
+            let mut jobs = self.jobs.lock().unwrap();
+            let current = jobs.get(quest_id).is_some_and(|j| j.run == run);
+            current && jobs.remove(quest_id).is_some()
+        };
+        if removed {
+            self.flush();
+        }
+    }
+
+    /// Write out progress updates that haven't been saved yet
+    pub fn flush_if_dirty(&self) {
+        if self.dirty.load(Ordering::Relaxed) {
+            self.flush();
+        }
+    }
+
+    /// Flush progress updates every [`FLUSH_INTERVAL`], for as long as the app runs
+    pub async fn run_flusher(self: Arc<Self>) {
+        let mut interval = tokio::time::interval(FLUSH_INTERVAL);
+        loop {
+            interval.tick().await;
+            self.flush_if_dirty();
+        }
+    }
+
+    /// Keep the jobs of runs stopped from now on, so they can be resumed after a restart
+    ///
+    /// Called on app exit, where quests are stopped only to save their position.
+    /// Pending progress is written now, and later updates are written as they come.
+    pub fn close(&self) {
+        self.closing.store(true, Ordering::Relaxed);
+        self.flush_if_dirty();
+    }
+
+    pub fn is_closing(&self) -> bool {
+        self.closing.load(Ordering::Relaxed)
+    }
+
+    pub fn remove(&self, quest_id: &str) -> Option<QuestJob> {
+        let job = self.jobs.lock().unwrap().remove(quest_id);
+        if job.is_some() {
+            self.flush();
+        }
+        job
+    }
+
+    pub fn get(&self, quest_id: &str) -> Option<QuestJob> {
+        self.jobs.lock().unwrap().get(quest_id).cloned()
+    }
+
+    /// All stored jobs, most recently updated first
+    pub fn list(&self) -> Vec<QuestJob> {
+        let mut jobs: Vec<_> = self.jobs.lock().unwrap().values().cloned().collect();
+        jobs.sort_by_key(|j| std::cmp::Reverse(j.updated_at));
+        jobs
+    }
+}
+
+/// Wraps a quest's event sink and mirrors its progress into a [`JobStore`]
+///
+/// The job is dropped once the quest completes or the user stops it; after an
+/// error it is kept so it can be resumed.
+pub struct PersistingEvents<E> {
+    inner: E,
+    store: Arc<JobStore>,
+    quest_id: String,
+    run: u64,
+}
+
+impl<E: QuestEvents> PersistingEvents<E> {
+    /// `run` is the value [`JobStore::save`] returned for this quest's job
+    pub fn new(inner: E, store: Arc<JobStore>, quest_id: String, run: u64) -> Self {
+        Self {
+            inner,
+            store,
+            quest_id,
+            run,
+        }
+    }
+}
+
+impl<E: QuestEvents> QuestEvents for PersistingEvents<E> {
+    fn emit(&self, event: QuestEvent) {
+        match &event {
+            QuestEvent::Progress(progress) => self.store.update_progress(&self.quest_id, self.run, *progress),
+            QuestEvent::Completed => self.store.finish(&self.quest_id, self.run),
+            QuestEvent::Stopped(flushed_progress) => {
+                if let Some(progress) = flushed_progress {
+                    self.store.update_progress(&self.quest_id, self.run, *progress);
+                }
+                if !self.store.is_closing() {
+                    self.store.finish(&self.quest_id, self.run);
+                }
+            }
+            _ => {}
+        }
+        self.inner.emit(event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::quest_events::RecordingEvents;
+
+    fn video_job(quest_id: &str) -> QuestJob {
+        QuestJob::new(
+            quest_id.to_string(),
+            JobTask::Video { speed_multiplier: 7.0, heartbeat_interval: 3 },
+            900,
+            0.0,
+        )
+    }
+
+    #[test]
+    fn test_jobs_survive_reopen() {
+        let dir = std::env::temp_dir().join(format!("dqh-jobs-test-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+
+        let store = JobStore::open(&dir);
+        let run = store.save(video_job("1"));
+        store.save(QuestJob::new("2".into(), JobTask::Game { application_id: "123".into() }, 900, 10.0));
+        store.remove("2");
+
+        // Progress only reaches the file on the next flush
+        store.update_progress("1", run, 42.5);
+        assert_eq!(JobStore::open(&dir).get("1").unwrap().progress, 0.0);
+        store.flush_if_dirty();
+
+        let reopened = JobStore::open(&dir);
+        let jobs = reopened.list();
+        assert_eq!(jobs.len(), 1);
+        assert_eq!(jobs[0].progress, 42.5);
+        assert_eq!(jobs[0].task.kind(), "video");
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_persisting_events_track_outcome() {
+        let store = Arc::new(JobStore::in_memory());
+        let run = store.save(video_job("1"));
+        let events = PersistingEvents::new(RecordingEvents::new(), store.clone(), "1".into(), run);
+
+        events.progress(50.0);
+        assert_eq!(store.get("1").unwrap().progress, 50.0);
+
+        // Errors keep the job around for a later resume
+        events.error("network down");
+        assert!(store.get("1").is_some());
+
+        events.completed();
+        assert!(store.get("1").is_none());
+    }
+
+    #[test]
+    fn test_replaced_run_leaves_new_job_alone() {
+        let store = Arc::new(JobStore::in_memory());
+        let old_run = store.save(video_job("1"));
+        let old = PersistingEvents::new(RecordingEvents::new(), store.clone(), "1".into(), old_run);
+        store.save(video_job("1"));
+
+        // The cancelled run reports late; the restarted job must survive it
+        old.progress(90.0);
+        old.stopped(Some(95.0));
+        assert_eq!(store.get("1").unwrap().progress, 0.0);
+    }
+
+    #[test]
+    fn test_quests_stopped_on_exit_stay_resumable() {
+        let store = Arc::new(JobStore::in_memory());
+        let run = store.save(video_job("1"));
+        let events = PersistingEvents::new(RecordingEvents::new(), store.clone(), "1".into(), run);
+
+        store.close();
+        events.stopped(Some(60.0));
+        assert_eq!(store.get("1").unwrap().progress, 60.0);
+    }
+}
@@ -0,0 +1,32 @@
+//! Core quest logic shared by the Tauri app and headless tooling
+//!
+//! Contains the Discord API client, models, quest completers, gateway client,
+//! game simulator and RPC activity mapping. Nothing in here depends on Tauri;
+//! progress is reported through the [`quest_events::QuestEvents`] trait and
+//! everything is logged through `tracing` (see [`logging`]).
+
+pub mod accounts;
+pub mod discord_api;
+pub mod discord_gateway;
+pub mod dispatch;
+pub mod endpoints;
+pub mod error;
+pub mod event_schema;
+pub mod game_simulator;
+pub mod jobs;
+pub mod logging;
+pub mod models;
+pub mod quest_completer;
+pub mod quest_events;
+pub mod queue;
+pub mod ratelimit;
+pub mod registry;
+pub mod retry;
+pub mod rpc;
+pub mod runner;
+pub mod secret;
+pub mod strategy;
+#[cfg(test)]
+mod test_support;
+pub mod vault;
+pub mod verify;
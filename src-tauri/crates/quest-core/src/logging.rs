@@ -0,0 +1,464 @@
+//! Log setup shared by the app and `dqh`
+//!
+//! Everything logs through `tracing`. [`install`] sets the global subscriber:
+//! human-readable lines on stderr, optionally a size-rotated [`RotatingFile`],
+//! and a [`RecentLogs`] buffer the app hands to the UI for bug reports. All of
+//! them mask token-shaped strings (see [`crate::secret::redact_tokens`]).
+
+use crate::secret::redact_tokens;
+use serde::{Serialize, Serializer};
+use std::collections::VecDeque;
+use std::fmt::Write as _;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::filter::{LevelFilter, Targets};
+use tracing_subscriber::fmt::MakeWriter;
+use tracing_subscriber::layer::{Context, Layer, SubscriberExt};
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::util::SubscriberInitExt;
+
+pub const LOG_FILE_NAME: &str = "discord-quest-helper.log";
+/// Size at which the log file is rotated
+pub const MAX_LOG_FILE_BYTES: u64 = 5 * 1024 * 1024;
+/// Rotated files kept next to the current one (`.1` is the most recent)
+pub const KEPT_LOG_FILES: usize = 3;
+/// Entries [`RecentLogs`] holds before dropping the oldest
+pub const RECENT_LOG_CAPACITY: usize = 2000;
+
+/// Crates whose debug output is noise in a bug report
+const QUIET_TARGETS: &[&str] = &["h2", "hyper", "hyper_util", "reqwest", "rustls", "tokio_tungstenite", "tungstenite"];
+
+/// Log file that starts over once it would grow past `max_bytes`
+///
+/// The full file is renamed to `<name>.1`, older copies shift up by one and
+/// anything past `keep` copies is deleted.
+pub struct RotatingFile {
+    path: PathBuf,
+    max_bytes: u64,
+    keep: usize,
+    state: Mutex<FileState>,
+}
+
+struct FileState {
+    file: File,
+    len: u64,
+}
+
+fn rotated_path(path: &Path, index: usize) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(format!(".{}", index));
+    path.with_file_name(name)
+}
+
+impl RotatingFile {
+    /// Append to `path`, creating it and its directory if needed
+    pub fn open(path: PathBuf, max_bytes: u64, keep: usize) -> io::Result<Self> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let len = file.metadata()?.len();
+        Ok(Self {
+            path,
+            max_bytes,
+            keep,
+            state: Mutex::new(FileState { file, len }),
+        })
+    }
+
+    /// [`LOG_FILE_NAME`] in `dir`, with the default size and copy limits
+    pub fn in_dir(dir: &Path) -> io::Result<Self> {
+        Self::open(dir.join(LOG_FILE_NAME), MAX_LOG_FILE_BYTES, KEPT_LOG_FILES)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn rotate(&self, state: &mut FileState) -> io::Result<()> {
+        let _ = fs::remove_file(rotated_path(&self.path, self.keep.max(1)));
+        for index in (1..self.keep).rev() {
+            let from = rotated_path(&self.path, index);
+            if from.exists() {
+                fs::rename(&from, rotated_path(&self.path, index + 1))?;
+            }
+        }
+        if self.keep > 0 {
+            fs::rename(&self.path, rotated_path(&self.path, 1))?;
+        }
+        state.file = File::create(&self.path)?;
+        state.len = 0;
+        Ok(())
+    }
+}
+
+impl Write for &RotatingFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut state = self.state.lock().unwrap();
+        if state.len > 0 && state.len + buf.len() as u64 > self.max_bytes {
+            self.rotate(&mut state)?;
+        }
+        let written = state.file.write(buf)?;
+        state.len += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.state.lock().unwrap().file.flush()
+    }
+}
+
+/// Writer wrapper that masks tokens before anything reaches the sink
+///
+/// The fmt layer writes each event as one buffer, so a token is never split
+/// across two writes.
+#[derive(Clone)]
+pub struct Redacting<M>(pub M);
+
+impl<'a, M: MakeWriter<'a>> MakeWriter<'a> for Redacting<M> {
+    type Writer = Redacting<M::Writer>;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        Redacting(self.0.make_writer())
+    }
+}
+
+impl<W: Write> Write for Redacting<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let text = String::from_utf8_lossy(buf);
+        self.0.write_all(redact_tokens(&text).as_bytes())?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.flush()
+    }
+}
+
+/// One log line as returned by `get_recent_logs`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LogEntry {
+    /// Unix time in milliseconds
+    pub timestamp_ms: u64,
+    #[serde(serialize_with = "serialize_level")]
+    pub level: Level,
+    /// Module that logged it, e.g. `quest_core::discord_api`
+    pub target: String,
+    /// Enclosing spans from the outermost in, e.g. `quest{quest_id=1}:request{action=Send heartbeat}`
+    pub spans: String,
+    /// Message followed by any extra fields as `key=value`
+    pub message: String,
+}
+
+fn serialize_level<S: Serializer>(level: &Level, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(level.as_str())
+}
+
+/// Collects `key=value` pairs, keeping the `message` field apart
+#[derive(Default)]
+struct FieldWriter {
+    message: String,
+    fields: String,
+}
+
+impl FieldWriter {
+    fn push(&mut self, field: &Field, value: &dyn std::fmt::Display) {
+        if field.name() == "message" {
+            let _ = write!(self.message, "{}", value);
+        } else {
+            if !self.fields.is_empty() {
+                self.fields.push(' ');
+            }
+            let _ = write!(self.fields, "{}={}", field.name(), value);
+        }
+    }
+
+    fn into_line(self) -> String {
+        match (self.message.is_empty(), self.fields.is_empty()) {
+            (_, true) => self.message,
+            (true, false) => self.fields,
+            (false, false) => format!("{} {}", self.message, self.fields),
+        }
+    }
+}
+
+impl Visit for FieldWriter {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.push(field, &value);
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.push(field, &format_args!("{:?}", value));
+    }
+}
+
+/// Fields of a span, rendered once when it's created or recorded into
+struct SpanFields(String);
+
+/// Ring buffer of the latest log entries, shared by every clone
+///
+/// Also a [`Layer`], so the same value is handed to the subscriber and kept
+/// around to answer [`RecentLogs::recent`].
+#[derive(Clone)]
+pub struct RecentLogs {
+    entries: Arc<Mutex<VecDeque<LogEntry>>>,
+    capacity: usize,
+}
+
+impl RecentLogs {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: Arc::new(Mutex::new(VecDeque::with_capacity(capacity))),
+            capacity,
+        }
+    }
+
+    /// Newest entries at `min_level` or more severe, oldest first, at most `limit` of them
+    pub fn recent(&self, min_level: Level, limit: Option<usize>) -> Vec<LogEntry> {
+        let entries = self.entries.lock().unwrap();
+        let mut matching: Vec<LogEntry> = entries
+            .iter()
+            .rev()
+            .filter(|e| e.level <= min_level)
+            .take(limit.unwrap_or(usize::MAX))
+            .cloned()
+            .collect();
+        matching.reverse();
+        matching
+    }
+
+    fn push(&self, entry: LogEntry) {
+        let mut entries = self.entries.lock().unwrap();
+        if entries.len() >= self.capacity {
+            entries.pop_front();
+        }
+        entries.push_back(entry);
+    }
+}
+
+impl<S> Layer<S> for RecentLogs
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else { return };
+        let mut fields = FieldWriter::default();
+        attrs.record(&mut fields);
+        span.extensions_mut().insert(SpanFields(fields.into_line()));
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else { return };
+        let mut fields = FieldWriter::default();
+        values.record(&mut fields);
+        let added = fields.into_line();
+        let mut extensions = span.extensions_mut();
+        match extensions.get_mut::<SpanFields>() {
+            Some(SpanFields(existing)) if !existing.is_empty() => {
+                existing.push(' ');
+                existing.push_str(&added);
+            }
+            Some(SpanFields(existing)) => *existing = added,
+            None => extensions.insert(SpanFields(added)),
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let mut spans = String::new();
+        if let Some(scope) = ctx.event_scope(event) {
+            for span in scope.from_root() {
+                if !spans.is_empty() {
+                    spans.push(':');
+                }
+                spans.push_str(span.name());
+                if let Some(SpanFields(fields)) = span.extensions().get::<SpanFields>() {
+                    if !fields.is_empty() {
+                        let _ = write!(spans, "{{{}}}", fields);
+                    }
+                }
+            }
+        }
+
+        let mut fields = FieldWriter::default();
+        event.record(&mut fields);
+        let metadata = event.metadata();
+        self.push(LogEntry {
+            timestamp_ms: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_millis() as u64)
+                .unwrap_or(0),
+            level: *metadata.level(),
+            target: metadata.target().to_string(),
+            spans: redact_tokens(&spans).into_owned(),
+            message: redact_tokens(&fields.into_line()).into_owned(),
+        });
+    }
+}
+
+/// Subscriber writing to `console`, `file` and `recent`, everything through [`Redacting`]
+///
+/// `level` applies to everything except the HTTP and websocket stack, which
+/// only gets through at warnings.
+pub(crate) fn subscriber<C>(level: Level, console: C, file: Option<RotatingFile>, recent: RecentLogs) -> impl Subscriber + Send + Sync
+where
+    C: for<'a> MakeWriter<'a> + Send + Sync + 'static,
+{
+    let filter = QUIET_TARGETS.iter().fold(
+        Targets::new().with_default(LevelFilter::from_level(level)),
+        |filter, target| filter.with_target(*target, LevelFilter::WARN.min(LevelFilter::from_level(level))),
+    );
+    let file_layer = file.map(|file| {
+        tracing_subscriber::fmt::layer()
+            .with_ansi(false)
+            .with_writer(Redacting(Arc::new(file)))
+    });
+
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(tracing_subscriber::fmt::layer().with_writer(Redacting(console)))
+        .with(file_layer)
+        .with(recent)
+}
+
+/// Set the global subscriber and return the buffer it fills
+///
+/// If a subscriber is already set (e.g. a test harness), that one is kept and
+/// the returned buffer stays empty.
+pub fn install(level: Level, file: Option<RotatingFile>) -> RecentLogs {
+    let recent = RecentLogs::new(RECENT_LOG_CAPACITY);
+    if let Err(e) = subscriber(level, io::stderr, file, recent.clone()).try_init() {
+        tracing::warn!("Logging was already set up, keeping the existing subscriber: {}", e);
+    }
+    recent
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::secret::{SecretString, REDACTED};
+    use crate::test_support::mock_client_with_token;
+    use mock_discord::{MockDiscord, Route, ScriptedResponse};
+    use tracing::{debug, error, info, info_span, warn};
+
+    const TOKEN: &str = "MTIzNDU2Nzg5MDEyMzQ1Njc4OTA.GhIjKl.abcdefghijklmnopqrstuvwxyz0123456";
+
+    /// Keeps everything written to it, like the log file would
+    #[derive(Default)]
+    struct Sink(Mutex<Vec<u8>>);
+
+    impl Write for &Sink {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl Sink {
+        fn contents(&self) -> String {
+            String::from_utf8(self.0.lock().unwrap().clone()).unwrap()
+        }
+    }
+
+    fn assert_no_token(sink: &Sink, recent: &RecentLogs) {
+        let written = sink.contents();
+        assert!(written.contains(REDACTED), "nothing was redacted in {:?}", written);
+        assert!(!written.contains(TOKEN));
+        for entry in recent.recent(Level::TRACE, None) {
+            assert!(!entry.message.contains(TOKEN) && !entry.spans.contains(TOKEN), "{:?}", entry);
+        }
+    }
+
+    #[test]
+    fn test_tokens_never_reach_log_sinks() {
+        let sink = Arc::new(Sink::default());
+        let recent = RecentLogs::new(RECENT_LOG_CAPACITY);
+        tracing::subscriber::with_default(subscriber(Level::TRACE, sink.clone(), None, recent.clone()), || {
+            let _login = info_span!("login", token = TOKEN).entered();
+            info!("Authorization: {}", TOKEN);
+            debug!(header = ?format!("Bearer {}", TOKEN), "sending");
+            warn!(secret = ?SecretString::from(TOKEN), "holding {}", SecretString::from(TOKEN));
+        });
+
+        assert_eq!(recent.recent(Level::TRACE, None).len(), 3);
+        assert_no_token(&sink, &recent);
+    }
+
+    #[tokio::test]
+    async fn test_echoed_token_is_redacted_in_logs_and_errors() {
+        let mock = MockDiscord::start().await;
+        mock.expect_token(None);
+        mock.script(
+            Route::CurrentUser,
+            ScriptedResponse::Json(401, serde_json::json!({ "message": format!("Bad token {}", TOKEN), "code": 0 })),
+        );
+        let client = mock_client_with_token(&mock, TOKEN);
+
+        let sink = Arc::new(Sink::default());
+        let recent = RecentLogs::new(RECENT_LOG_CAPACITY);
+        let _guard = tracing::subscriber::set_default(subscriber(Level::TRACE, sink.clone(), None, recent.clone()));
+        let e = client.get_current_user().await.unwrap_err();
+        error!("Login failed: {}", e);
+
+        assert!(!e.to_string().contains(TOKEN));
+        assert!(!serde_json::to_string(&e).unwrap().contains(TOKEN));
+        assert_no_token(&sink, &recent);
+    }
+
+    #[test]
+    fn test_log_file_rotates_by_size() {
+        let dir = std::env::temp_dir().join(format!("dqh-log-test-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let file = RotatingFile::open(dir.join("test.log"), 100, 2).unwrap();
+
+        let line = [b'x'; 39];
+        for _ in 0..10 {
+            (&file).write_all(&line).unwrap();
+            (&file).write_all(b"\n").unwrap();
+        }
+
+        let current = fs::metadata(file.path()).unwrap().len();
+        assert!(current > 0 && current <= 100);
+        assert_eq!(fs::metadata(dir.join("test.log.1")).unwrap().len(), 80);
+        assert!(dir.join("test.log.2").exists());
+        assert!(!dir.join("test.log.3").exists());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_recent_logs_filter_by_level() {
+        let recent = RecentLogs::new(3);
+        let subscriber = tracing_subscriber::registry().with(recent.clone());
+        tracing::subscriber::with_default(subscriber, || {
+            info!("dropped by capacity");
+            let quest = info_span!("quest", quest_id = "1");
+            let _quest = quest.enter();
+            let request = info_span!("request", action = "Send heartbeat", status = tracing::field::Empty);
+            request.record("status", 200);
+            let _request = request.enter();
+            debug!("noisy");
+            info!(attempt = 2, "sent");
+            warn!("slow");
+        });
+
+        let entries = recent.recent(Level::INFO, None);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].message, "sent attempt=2");
+        assert_eq!(entries[0].spans, "quest{quest_id=1}:request{action=Send heartbeat status=200}");
+        assert_eq!(entries[1].level, Level::WARN);
+
+        let latest = recent.recent(Level::TRACE, Some(1));
+        assert_eq!(latest.len(), 1);
+        assert_eq!(latest[0].message, "slow");
+        assert_eq!(serde_json::to_value(&latest[0]).unwrap()["level"], "WARN");
+    }
+}
@@ -0,0 +1,500 @@
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use tracing::warn;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiscordUser {
+    pub id: String,
+    pub username: String,
+    pub discriminator: String,
+    pub avatar: Option<String>,
+    pub global_name: Option<String>,
+}
+
+/// Base URL quest assets and reward images are served from
+pub const CDN_BASE: &str = "https://cdn.discordapp.com";
+
+/// Quest as returned by `/quests/@me`
+///
+/// Mirrors the API shape, so it serializes back into what the frontend
+/// already reads. Fields that aren't modelled are kept in `extra` at every
+/// level, and new ones Discord adds don't break deserialization. The gateway
+/// READY payload spells some keys in camelCase; those are accepted as aliases.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Quest {
+    pub id: String,
+    pub config: QuestConfig,
+    /// `null` until the user enrolls
+    #[serde(default, alias = "userStatus")]
+    pub user_status: Option<QuestUserStatus>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QuestConfig {
+    #[serde(default, alias = "startsAt", skip_serializing_if = "Option::is_none")]
+    pub starts_at: Option<String>,
+    #[serde(default, alias = "expiresAt", skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub application: Option<QuestApplication>,
+    #[serde(default)]
+    pub messages: QuestMessages,
+    #[serde(default, alias = "taskConfigV2", skip_serializing_if = "Option::is_none")]
+    pub task_config_v2: Option<TaskConfig>,
+    /// Older quests only have the v1 task config
+    #[serde(default, alias = "taskConfig", skip_serializing_if = "Option::is_none")]
+    pub task_config: Option<TaskConfig>,
+    #[serde(default, alias = "rewardsConfig", skip_serializing_if = "Option::is_none")]
+    pub rewards_config: Option<RewardsConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub assets: Option<QuestAssets>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QuestApplication {
+    pub id: String,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub link: Option<String>,
+    /// Icon hash, see [`QuestApplication::icon_url`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl QuestApplication {
+    pub fn icon_url(&self) -> Option<String> {
+        self.icon
+            .as_ref()
+            .map(|icon| format!("{}/app-icons/{}/{}.png", CDN_BASE, self.id, icon))
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct QuestMessages {
+    #[serde(default, alias = "questName")]
+    pub quest_name: String,
+    #[serde(default, alias = "gameTitle", skip_serializing_if = "Option::is_none")]
+    pub game_title: Option<String>,
+    #[serde(default, alias = "gamePublisher", skip_serializing_if = "Option::is_none")]
+    pub game_publisher: Option<String>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TaskConfig {
+    /// Keyed by task name, e.g. `WATCH_VIDEO` or `PLAY_ON_DESKTOP`
+    #[serde(default)]
+    pub tasks: BTreeMap<String, QuestTask>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QuestTask {
+    /// Seconds (or count, for non-time tasks) needed to complete the task
+    #[serde(default, deserialize_with = "lenient_u32")]
+    pub target: u32,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// Count that may arrive as a float (`900.0`), a string or out of range; rounded and clamped
+fn lenient_u32<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u32, D::Error> {
+    let number = match Value::deserialize(deserializer)? {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => s.trim().parse::<f64>().ok(),
+        _ => None,
+    };
+    Ok(number
+        .filter(|n| n.is_finite())
+        .map(|n| n.round().clamp(0.0, u32::MAX as f64) as u32)
+        .unwrap_or(0))
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RewardsConfig {
+    #[serde(default)]
+    pub rewards: Vec<QuestReward>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QuestReward {
+    /// Reward type as a Discord enum value (e.g. 3 for a collectible, 4 for orbs)
+    #[serde(default, rename = "type", skip_serializing_if = "Option::is_none")]
+    pub reward_type: Option<u32>,
+    #[serde(default)]
+    pub messages: RewardMessages,
+    /// CDN path of the reward image, see [`cdn_url`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub asset: Option<String>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl QuestReward {
+    pub fn asset_url(&self) -> Option<String> {
+        self.asset.as_deref().map(cdn_url)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RewardMessages {
+    /// Human readable reward, e.g. "700 Orbs"
+    #[serde(default)]
+    pub name: String,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// CDN paths of the quest artwork, see [`cdn_url`]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct QuestAssets {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hero: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hero_video: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub game_tile: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub logotype: Option<String>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct QuestUserStatus {
+    #[serde(default, alias = "enrolledAt")]
+    pub enrolled_at: Option<String>,
+    #[serde(default, alias = "completedAt")]
+    pub completed_at: Option<String>,
+    #[serde(default, alias = "claimedAt")]
+    pub claimed_at: Option<String>,
+    /// Keyed by task name; a task only shows up once it has some progress
+    #[serde(default)]
+    pub progress: BTreeMap<String, TaskProgress>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TaskProgress {
+    #[serde(default)]
+    pub value: f64,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl QuestUserStatus {
+    /// Seconds done on `task`, if Discord reported any
+    pub fn task_progress(&self, task: &str) -> Option<f64> {
+        self.progress.get(task).map(|p| p.value)
+    }
+
+    pub fn is_completed(&self) -> bool {
+        self.completed_at.is_some()
+    }
+}
+
+/// Full URL of an asset path from [`QuestAssets`] or [`QuestReward::asset`]
+pub fn cdn_url(path: &str) -> String {
+    format!("{}/{}", CDN_BASE, path.trim_start_matches('/'))
+}
+
+impl Quest {
+    pub fn name(&self) -> &str {
+        if self.config.messages.quest_name.is_empty() {
+            "Unknown Quest"
+        } else {
+            &self.config.messages.quest_name
+        }
+    }
+
+    /// The v2 task config, falling back to the legacy one
+    pub fn tasks(&self) -> &BTreeMap<String, QuestTask> {
+        static EMPTY: BTreeMap<String, QuestTask> = BTreeMap::new();
+        self.config
+            .task_config_v2
+            .as_ref()
+            .or(self.config.task_config.as_ref())
+            .map(|c| &c.tasks)
+            .unwrap_or(&EMPTY)
+    }
+
+    /// Seconds done on `task` so far
+    pub fn task_progress(&self, task: &str) -> f64 {
+        self.user_status
+            .as_ref()
+            .and_then(|s| s.task_progress(task))
+            .unwrap_or(0.0)
+    }
+
+    /// Progress on `task` as a percentage of its target
+    pub fn task_percent(&self, task: &str) -> f64 {
+        match self.tasks().get(task) {
+            Some(t) if t.target > 0 => (self.task_progress(task) / t.target as f64 * 100.0).min(100.0),
+            _ => 0.0,
+        }
+    }
+
+    pub fn is_enrolled(&self) -> bool {
+        self.user_status.as_ref().is_some_and(|s| s.enrolled_at.is_some())
+    }
+
+    pub fn is_completed(&self) -> bool {
+        self.user_status.as_ref().is_some_and(|s| s.is_completed())
+    }
+
+    pub fn application_id(&self) -> Option<&str> {
+        self.config.application.as_ref().map(|a| a.id.as_str())
+    }
+
+    pub fn hero_url(&self) -> Option<String> {
+        self.config.assets.as_ref()?.hero.as_deref().map(cdn_url)
+    }
+
+    /// Reward names, e.g. `["700 Orbs"]`
+    pub fn reward_names(&self) -> Vec<&str> {
+        self.config
+            .rewards_config
+            .iter()
+            .flat_map(|c| &c.rewards)
+            .map(|r| r.messages.name.as_str())
+            .collect()
+    }
+}
+
+/// Quest as sent to the frontend: the API shape plus the fields derived from it
+///
+/// Keeps CDN URL building and progress math on this side, so the UI doesn't
+/// have to repeat them.
+#[derive(Debug, Clone, Serialize)]
+pub struct QuestView {
+    #[serde(flatten)]
+    pub quest: Quest,
+    /// Percentage done per task, keyed like [`Quest::tasks`]
+    pub progress: BTreeMap<String, f64>,
+    pub hero_url: Option<String>,
+    pub icon_url: Option<String>,
+    /// Same order as `config.rewards_config.rewards`
+    pub rewards: Vec<RewardView>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RewardView {
+    pub name: String,
+    pub asset_url: Option<String>,
+}
+
+impl From<Quest> for QuestView {
+    fn from(quest: Quest) -> Self {
+        let progress = quest
+            .tasks()
+            .keys()
+            .map(|task| (task.clone(), quest.task_percent(task)))
+            .collect();
+        let rewards = quest
+            .config
+            .rewards_config
+            .iter()
+            .flat_map(|c| &c.rewards)
+            .map(|r| RewardView {
+                name: r.messages.name.clone(),
+                asset_url: r.asset_url(),
+            })
+            .collect();
+        QuestView {
+            progress,
+            hero_url: quest.hero_url(),
+            icon_url: quest.config.application.as_ref().and_then(|a| a.icon_url()),
+            rewards,
+            quest,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DetectableGame {
+    pub id: String,
+    pub name: String,
+    pub executables: Vec<GameExecutable>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameExecutable {
+    pub name: String,
+    pub os: String,
+}
+
+/// Body of `/quests/@me`; quests are parsed one by one so a bad entry can be skipped
+#[derive(Debug, Deserialize)]
+pub struct QuestsResponse {
+    #[serde(default)]
+    pub quests: Vec<Value>,
+}
+
+/// Parse a quest list from the API or the gateway, skipping (and logging) entries that don't parse
+pub fn parse_quests(values: &[Value]) -> Vec<Quest> {
+    values
+        .iter()
+        .filter_map(|q| match Quest::deserialize(q) {
+            Ok(quest) => Some(quest),
+            Err(e) => {
+                let id = q.get("id").and_then(|i| i.as_str()).unwrap_or("?");
+                warn!("Skipping quest {} that failed to parse: {}", id, e);
+                None
+            }
+        })
+        .collect()
+}
+
+#[derive(Debug, Serialize)]
+pub struct VideoProgressPayload {
+    pub timestamp: f64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct HeartbeatPayload {
+    pub stream_key: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct GameHeartbeatPayload {
+    pub application_id: String,
+    pub terminal: bool,
+}
+
+/// Account found by token detection, as shown to the UI
+///
+/// The token itself stays in an [`crate::accounts::AccountStore`]; `handle`
+/// is what the UI passes back to log in as this account.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExtractedAccount {
+    pub handle: String,
+    pub user: DiscordUser,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn api_quest() -> Value {
+        json!({
+            "id": "1",
+            "preview": false,
+            "config": {
+                "config_version": 2,
+                "starts_at": "2025-01-01T00:00:00+00:00",
+                "expires_at": "2025-02-01T00:00:00+00:00",
+                "application": { "id": "42", "name": "Game", "link": "https://example.invalid", "icon": "abc" },
+                "messages": { "quest_name": "Play Game", "game_title": "Game", "game_publisher": "Studio" },
+                "task_config_v2": {
+                    "type": 1,
+                    "join_operator": "or",
+                    "tasks": {
+                        "PLAY_ON_DESKTOP": { "type": "PLAY_ON_DESKTOP", "target": 900 },
+                        "WATCH_VIDEO": { "type": "WATCH_VIDEO", "target": 30, "assets": { "video": {} } },
+                    },
+                },
+                "rewards_config": {
+                    "assignment_method": 1,
+                    "rewards": [{ "type": 4, "sku_id": "7", "messages": { "name": "700 Orbs" }, "asset": "quests/1/orbs.png" }],
+                },
+                "assets": { "hero": "quests/1/hero.png", "logotype": "quests/1/logo.png" },
+                "cosponsor_metadata": null,
+            },
+            "user_status": {
+                "enrolled_at": "2025-01-02T00:00:00+00:00",
+                "completed_at": null,
+                "claimed_at": null,
+                "progress": { "PLAY_ON_DESKTOP": { "event_name": "PLAY_ON_DESKTOP", "value": 450.0, "updated_at": null } },
+            },
+        })
+    }
+
+    #[test]
+    fn test_quest_keeps_every_task_and_unknown_fields() {
+        let quest: Quest = serde_json::from_value(api_quest()).unwrap();
+
+        assert_eq!(quest.name(), "Play Game");
+        assert_eq!(quest.tasks().len(), 2);
+        assert_eq!(quest.tasks()["WATCH_VIDEO"].target, 30);
+        assert_eq!(quest.task_percent("PLAY_ON_DESKTOP"), 50.0);
+        assert_eq!(quest.task_percent("WATCH_VIDEO"), 0.0);
+        assert!(quest.is_enrolled());
+        assert!(!quest.is_completed());
+        assert_eq!(quest.reward_names(), vec!["700 Orbs"]);
+        assert_eq!(quest.hero_url().as_deref(), Some("https://cdn.discordapp.com/quests/1/hero.png"));
+        assert_eq!(
+            quest.config.application.as_ref().unwrap().icon_url().as_deref(),
+            Some("https://cdn.discordapp.com/app-icons/42/abc.png")
+        );
+
+        // Serializes back to the API shape, unknown fields included
+        assert_eq!(serde_json::to_value(&quest).unwrap(), api_quest());
+    }
+
+    #[test]
+    fn test_quest_view_adds_derived_fields() {
+        let quest: Quest = serde_json::from_value(api_quest()).unwrap();
+        let view = serde_json::to_value(QuestView::from(quest)).unwrap();
+
+        assert_eq!(view["progress"], json!({ "PLAY_ON_DESKTOP": 50.0, "WATCH_VIDEO": 0.0 }));
+        assert_eq!(view["hero_url"], "https://cdn.discordapp.com/quests/1/hero.png");
+        assert_eq!(view["icon_url"], "https://cdn.discordapp.com/app-icons/42/abc.png");
+        assert_eq!(
+            view["rewards"],
+            json!([{ "name": "700 Orbs", "asset_url": "https://cdn.discordapp.com/quests/1/orbs.png" }])
+        );
+        // The API fields are still there for everything else the UI reads
+        assert_eq!(view["config"], api_quest()["config"]);
+        assert_eq!(view["user_status"], api_quest()["user_status"]);
+    }
+
+    #[test]
+    fn test_gateway_quest_uses_camel_case() {
+        let quest: Quest = serde_json::from_value(json!({
+            "id": "1",
+            "config": {
+                "expiresAt": "2025-02-01T00:00:00+00:00",
+                "messages": { "questName": "Watch" },
+                "taskConfigV2": { "tasks": { "WATCH_VIDEO": { "target": 30 } } },
+            },
+            "userStatus": { "enrolledAt": "2025-01-02T00:00:00+00:00", "completedAt": null, "progress": {} },
+        }))
+        .unwrap();
+
+        assert_eq!(quest.name(), "Watch");
+        assert_eq!(quest.config.expires_at.as_deref(), Some("2025-02-01T00:00:00+00:00"));
+        assert_eq!(quest.tasks()["WATCH_VIDEO"].target, 30);
+        assert!(quest.is_enrolled());
+    }
+
+    #[test]
+    fn test_odd_targets_and_bad_quests_do_not_sink_the_list() {
+        let mut float_target = api_quest();
+        float_target["config"]["task_config_v2"]["tasks"]["PLAY_ON_DESKTOP"]["target"] = json!(900.0);
+        let mut huge_target = api_quest();
+        huge_target["id"] = json!("2");
+        huge_target["config"]["task_config_v2"]["tasks"]["PLAY_ON_DESKTOP"]["target"] = json!(1e12);
+        let broken = json!({ "id": "3", "config": "not an object" });
+
+        let quests = parse_quests(&[float_target, broken, huge_target]);
+
+        let ids: Vec<_> = quests.iter().map(|q| q.id.as_str()).collect();
+        assert_eq!(ids, vec!["1", "2"]);
+        assert_eq!(quests[0].tasks()["PLAY_ON_DESKTOP"].target, 900);
+        assert_eq!(quests[1].tasks()["PLAY_ON_DESKTOP"].target, u32::MAX);
+    }
+}
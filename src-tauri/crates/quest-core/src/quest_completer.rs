@@ -0,0 +1,732 @@
+use crate::discord_api::DiscordApiClient;
+use crate::error::DiscordApiError;
+use crate::quest_events::QuestEvents;
+use crate::retry::{RetryNotice, RetryPolicy};
+use crate::strategy::{GameHeartbeatStrategy, QuestStrategy, StreamStrategy, VideoStrategy};
+use anyhow::Result;
+use std::time::Duration;
+use tokio::sync::mpsc::error::TryRecvError;
+use tokio::sync::mpsc::Receiver;
+use tokio::time::{sleep_until, Instant};
+use tracing::{error, info, warn};
+
+/// Message sent to a running completer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuestControl {
+    /// Stop for good (the same as dropping the sender)
+    Stop,
+    /// Stop sending requests but keep the current position
+    Pause,
+    /// Continue a paused quest where it left off
+    Resume,
+}
+
+/// Block while paused; returns false if the quest was stopped instead of resumed
+async fn wait_while_paused<E: QuestEvents + ?Sized>(control_rx: &mut Receiver<QuestControl>, events: &E) -> bool {
+    info!("Quest paused");
+    events.paused();
+    loop {
+        match control_rx.recv().await {
+            Some(QuestControl::Resume) => {
+                info!("Quest resumed");
+                events.resumed();
+                return true;
+            }
+            Some(QuestControl::Pause) => {}
+            Some(QuestControl::Stop) | None => return false,
+        }
+    }
+}
+
+/// Handle control messages that arrived since the last check; returns false if the quest should stop
+async fn check_control<E: QuestEvents + ?Sized>(control_rx: &mut Receiver<QuestControl>, events: &E) -> bool {
+    loop {
+        match control_rx.try_recv() {
+            Ok(QuestControl::Pause) => {
+                if !wait_while_paused(control_rx, events).await {
+                    return false;
+                }
+            }
+            Ok(QuestControl::Resume) => {}
+            Err(TryRecvError::Empty) => return true,
+            Ok(QuestControl::Stop) | Err(TryRecvError::Disconnected) => return false,
+        }
+    }
+}
+
+/// Sleep for `duration` while handling control messages; returns false if the quest should stop
+///
+/// Time spent paused doesn't count, so a resumed quest waits out only what was left.
+pub(crate) async fn wait<E: QuestEvents + ?Sized>(duration: Duration, control_rx: &mut Receiver<QuestControl>, events: &E) -> bool {
+    let mut active = Duration::ZERO;
+    wait_counting(duration, control_rx, events, &mut active).await
+}
+
+/// [`wait`], adding the time actually waited (paused time left out) to `active`
+///
+/// A wait cut short by a stop adds only what had passed until then.
+async fn wait_counting<E: QuestEvents + ?Sized>(
+    duration: Duration,
+    control_rx: &mut Receiver<QuestControl>,
+    events: &E,
+    active: &mut Duration,
+) -> bool {
+    let mut deadline = Instant::now() + duration;
+    loop {
+        tokio::select! {
+            _ = sleep_until(deadline) => {
+                *active += duration;
+                return true;
+            }
+            msg = control_rx.recv() => match msg {
+                Some(QuestControl::Pause) => {
+                    let remaining = deadline.saturating_duration_since(Instant::now());
+                    if !wait_while_paused(control_rx, events).await {
+                        *active += duration.saturating_sub(remaining);
+                        return false;
+                    }
+                    deadline = Instant::now() + remaining;
+                }
+                Some(QuestControl::Resume) => {}
+                Some(QuestControl::Stop) | None => {
+                    *active += duration.saturating_sub(deadline.saturating_duration_since(Instant::now()));
+                    return false;
+                }
+            }
+        }
+    }
+}
+
+/// What a completer should do after a failed request
+enum AfterFailure {
+    /// Send the same request again
+    Retry,
+    /// Cancelled while waiting to retry
+    Cancelled,
+    /// Fatal error or too many failures in a row; `quest-error` was already emitted
+    Abort(anyhow::Error),
+}
+
+/// Apply `policy` to a failed request: emit `quest-retrying` and wait, or give up
+async fn handle_failure<E: QuestEvents + ?Sized>(
+    policy: &RetryPolicy,
+    failures: &mut u32,
+    error: DiscordApiError,
+    what: &str,
+    events: &E,
+    control_rx: &mut Receiver<QuestControl>,
+) -> AfterFailure {
+    *failures += 1;
+
+    let Some(delay) = policy.next_delay(*failures, &error) else {
+        error!("{} failed: {}", what, error);
+        events.error(&error.to_string());
+        return AfterFailure::Abort(error.into());
+    };
+
+    warn!("{} failed ({}/{}), retrying in {:.1}s: {}",
+          what, failures, policy.max_consecutive_failures, delay.as_secs_f64(), error);
+    events.retrying(&RetryNotice {
+        attempt: *failures,
+        max_attempts: policy.max_consecutive_failures,
+        delay_secs: delay.as_secs_f64(),
+        message: error.to_string(),
+    });
+
+    if wait(delay, control_rx, events).await {
+        AfterFailure::Retry
+    } else {
+        AfterFailure::Cancelled
+    }
+}
+
+/// Accepted requests in a row without server-side progress before a quest is given up
+const MAX_STALLED_STEPS: u32 = 10;
+
+/// Close the strategy's session, if it keeps one, and emit whether Discord acknowledged it
+///
+/// Sent once, without retries: the run is over either way.
+async fn end_session<S: QuestStrategy, E: QuestEvents + ?Sized>(client: &DiscordApiClient, strategy: &S, events: &E) {
+    let Some(result) = strategy.end_session(client).await else {
+        return;
+    };
+    match result {
+        Ok(()) => {
+            info!("{} session closed", strategy.name());
+            events.session_closed(true);
+        }
+        Err(e) => {
+            warn!("{} session close was not acknowledged: {}", strategy.name(), e);
+            events.session_closed(false);
+        }
+    }
+}
+
+/// End a stopped run: save its position, close its session, then emit `stopped`
+///
+/// The flush is a single request; if it fails, the last accepted step stands.
+/// `accepted` is whether Discord took any request of this run; without one
+/// (and without a flush) there is no session to close.
+async fn cancel<S: QuestStrategy, E: QuestEvents + ?Sized>(
+    client: &DiscordApiClient,
+    strategy: &mut S,
+    events: &E,
+    accepted: bool,
+) -> Result<()> {
+    info!("{} quest cancelled", strategy.name());
+    let flushed = match strategy.flush(client).await {
+        Some(Ok(response)) => {
+            let progress = strategy.interpret(response);
+            info!("{} quest final progress saved: {:.1}%", strategy.name(), progress);
+            Some(progress)
+        }
+        Some(Err(e)) => {
+            warn!("{} quest final progress could not be saved: {}", strategy.name(), e);
+            None
+        }
+        None => None,
+    };
+    if accepted || flushed.is_some() {
+        end_session(client, strategy, events).await;
+    }
+    events.stopped(flushed);
+    Ok(())
+}
+
+/// Drive `strategy` until it is done, stopped, or fails for good
+///
+/// Handles stop and pause between steps, retries failed steps under
+/// [`RetryPolicy::default`] and emits progress, completion and error events.
+/// A run that completes or is stopped closes its session first (see
+/// [`QuestStrategy::end_session`]), as long as Discord accepted one of its
+/// requests; a quest that was already done on entry sends nothing.
+pub async fn run_strategy<S: QuestStrategy, E: QuestEvents + ?Sized>(
+    client: &DiscordApiClient,
+    strategy: &mut S,
+    events: &E,
+    control_rx: &mut Receiver<QuestControl>,
+) -> Result<()> {
+    let name = strategy.name();
+    let retry_policy = RetryPolicy::default();
+    let mut failures = 0;
+    let mut last_progress = f64::NEG_INFINITY;
+    let mut stalled = 0;
+    let mut accepted = false;
+
+    while !strategy.is_done() {
+        // Check stop / pause signals
+        if !check_control(control_rx, events).await {
+            return cancel(client, strategy, events, accepted).await;
+        }
+
+        match strategy.next_request(client).await {
+            Ok(response) => {
+                failures = 0;
+                accepted = true;
+                let progress = strategy.interpret(response);
+                events.progress(progress);
+                info!("{} quest progress: {:.1}%", name, progress);
+
+                if strategy.is_done() {
+                    break;
+                }
+
+                // Progress comes from Discord, so guard against it never moving
+                if progress > last_progress {
+                    last_progress = progress;
+                    stalled = 0;
+                } else {
+                    stalled += 1;
+                    if stalled >= MAX_STALLED_STEPS {
+                        let message = format!("Discord stopped crediting progress at {:.1}%", progress);
+                        error!("{} quest: {}", name, message);
+                        events.error(&message);
+                        anyhow::bail!(message);
+                    }
+                }
+            }
+            Err(e) => {
+                let what = format!("{} quest request", name);
+                match handle_failure(&retry_policy, &mut failures, e, &what, events, control_rx).await {
+                    AfterFailure::Retry => continue,
+                    AfterFailure::Cancelled => return cancel(client, strategy, events, accepted).await,
+                    AfterFailure::Abort(e) => return Err(e),
+                }
+            }
+        }
+
+        // Wait before the next step (paused time doesn't count)
+        let mut active = Duration::ZERO;
+        let keep_going = wait_counting(strategy.interval(), control_rx, events, &mut active).await;
+        strategy.advance(active);
+        if !keep_going {
+            return cancel(client, strategy, events, accepted).await;
+        }
+    }
+
+    if accepted {
+        end_session(client, strategy, events).await;
+    }
+    events.completed();
+    info!("{} quest completed!", name);
+    Ok(())
+}
+
+/// Complete a video quest
+/// 
+/// Simulates watching a video by incrementally sending video progress
+#[allow(clippy::too_many_arguments)]
+pub async fn complete_video_quest<E: QuestEvents + ?Sized>(
+    client: &DiscordApiClient,
+    quest_id: String,
+    seconds_needed: u32,
+    initial_progress: f64,
+    speed_multiplier: f64,
+    heartbeat_interval: u64,
+    events: &E,
+    control_rx: &mut Receiver<QuestControl>,
+) -> Result<()> {
+    info!("Starting video quest: quest_id={}, target={}s, current_progress={:.1}%", 
+          quest_id, seconds_needed, initial_progress);
+
+    let mut strategy = VideoStrategy::new(quest_id, seconds_needed, initial_progress, speed_multiplier, heartbeat_interval);
+    run_strategy(client, &mut strategy, events, control_rx).await
+}
+
+/// Complete a stream quest
+/// 
+/// Maintains streaming status by periodically sending heartbeats
+pub async fn complete_stream_quest<E: QuestEvents + ?Sized>(
+    client: &DiscordApiClient,
+    quest_id: String,
+    stream_key: String,
+    seconds_needed: u32,
+    initial_progress: f64,
+    events: &E,
+    control_rx: &mut Receiver<QuestControl>,
+) -> Result<()> {
+    let mut strategy = StreamStrategy::new(quest_id, stream_key, seconds_needed, initial_progress);
+    run_strategy(client, &mut strategy, events, control_rx).await
+}
+
+/// Complete a game quest by sending direct heartbeat requests
+/// 
+/// This is an alternative to running a simulated game executable.
+pub async fn complete_game_quest_via_heartbeat<E: QuestEvents + ?Sized>(
+    client: &DiscordApiClient,
+    quest_id: String,
+    application_id: String,
+    seconds_needed: u32,
+    initial_progress: f64,
+    events: &E,
+    control_rx: &mut Receiver<QuestControl>,
+) -> Result<()> {
+    info!("Starting game quest via heartbeat: quest_id={}, app_id={}, target={}s, interval={}s", 
+          quest_id, application_id, seconds_needed, GameHeartbeatStrategy::INTERVAL);
+
+    let mut strategy = GameHeartbeatStrategy::new(quest_id, application_id, seconds_needed, initial_progress);
+    run_strategy(client, &mut strategy, events, control_rx).await
+}
+
+/// Generate a random stream key (`stream_` + 32 alphanumeric chars)
+pub fn generate_stream_key() -> String {
+    use rand::Rng;
+    use rand::distr::Alphanumeric;
+
+    let random_string: String = rand::rng()
+        .sample_iter(Alphanumeric)
+        .take(32)
+        .map(char::from)
+        .collect();
+
+    format!("stream_{}", random_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::endpoints::DiscordEndpoints;
+    use crate::quest_events::{QuestEvent, RecordingEvents};
+    use crate::test_support::mock_client;
+    use mock_discord::{MockDiscord, QuestFixture, Route, ScriptedResponse};
+
+
+    #[tokio::test(start_paused = true)]
+    async fn test_video_quest_completes() {
+        let mock = MockDiscord::start().await;
+        mock.add_quest(QuestFixture::new("1", "WATCH_VIDEO", 30).enrolled());
+        let events = RecordingEvents::new();
+        let (_control_tx, mut control_rx) = tokio::sync::mpsc::channel(1);
+
+        complete_video_quest(&mock_client(&mock), "1".into(), 30, 0.0, 1.0, 10, &events, &mut control_rx)
+            .await
+            .unwrap();
+
+        let recorded = events.events();
+        assert_eq!(recorded.last(), Some(&QuestEvent::Completed));
+        assert_eq!(recorded.iter().filter(|e| matches!(e, QuestEvent::Progress(_))).count(), 3);
+        assert!(mock.quest("1").unwrap().completed);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_video_quest_reports_error() {
+        let mock = MockDiscord::start().await;
+        mock.add_quest(QuestFixture::new("1", "WATCH_VIDEO", 30).enrolled());
+        mock.script(Route::VideoProgress, ScriptedResponse::Unauthorized);
+        let events = RecordingEvents::new();
+        let (_control_tx, mut control_rx) = tokio::sync::mpsc::channel(1);
+
+        let result =
+            complete_video_quest(&mock_client(&mock), "1".into(), 30, 0.0, 1.0, 10, &events, &mut control_rx).await;
+
+        assert!(result.is_err());
+        assert!(matches!(events.events().as_slice(), [QuestEvent::Error(_)]));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_video_quest_pause_keeps_position() {
+        let mock = MockDiscord::start().await;
+        mock.add_quest(QuestFixture::new("1", "WATCH_VIDEO", 30).enrolled());
+        let events = RecordingEvents::new();
+        let (control_tx, mut control_rx) = tokio::sync::mpsc::channel(8);
+
+        let client = mock_client(&mock);
+        let task_events = events.clone();
+        let task = tokio::spawn(async move {
+            complete_video_quest(&client, "1".into(), 30, 0.0, 1.0, 10, &task_events, &mut control_rx).await
+        });
+
+        // First update goes out immediately, then pause halfway through the wait
+        tokio::time::sleep(Duration::from_secs(5)).await;
+        control_tx.send(QuestControl::Pause).await.unwrap();
+        tokio::time::sleep(Duration::from_secs(600)).await;
+        assert_eq!(mock.requests_to(Route::VideoProgress).len(), 1);
+
+        control_tx.send(QuestControl::Resume).await.unwrap();
+        task.await.unwrap().unwrap();
+
+        let recorded = events.events();
+        assert_eq!(recorded.iter().filter(|e| matches!(e, QuestEvent::Progress(_))).count(), 3);
+        assert!(recorded.contains(&QuestEvent::Paused));
+        assert!(recorded.contains(&QuestEvent::Resumed));
+        assert_eq!(recorded.last(), Some(&QuestEvent::Completed));
+        assert_eq!(mock.requests_to(Route::VideoProgress).len(), 3);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_video_quest_flushes_position_on_stop() {
+        let mock = MockDiscord::start().await;
+        mock.add_quest(QuestFixture::new("1", "WATCH_VIDEO", 60).enrolled());
+        let events = RecordingEvents::new();
+        let (control_tx, mut control_rx) = tokio::sync::mpsc::channel(1);
+
+        let client = mock_client(&mock);
+        let run = complete_video_quest(&client, "1".into(), 60, 0.0, 1.0, 10, &events, &mut control_rx);
+        let stop = async {
+            // Small steps, so the paused clock doesn't jump past the first request
+            while events.events().is_empty() {
+                tokio::time::sleep(Duration::from_millis(1)).await;
+            }
+            tokio::time::sleep(Duration::from_secs(5)).await;
+            control_tx.send(QuestControl::Stop).await.unwrap();
+        };
+        let (result, _) = tokio::join!(run, stop);
+        result.unwrap();
+
+        // Accepted ~10s at the first step, then 5s more were watched before the stop
+        let updates = mock.requests_to(Route::VideoProgress);
+        assert_eq!(updates.len(), 2);
+        let first = updates[0].body["timestamp"].as_f64().unwrap();
+        let flushed = updates[1].body["timestamp"].as_f64().unwrap();
+        assert!((flushed - first - 5.0).abs() < 0.1);
+
+        let Some(QuestEvent::Stopped(Some(progress))) = events.events().last().cloned() else {
+            panic!("expected a stop with flushed progress");
+        };
+        assert!((progress - flushed / 60.0 * 100.0).abs() < 0.01);
+        assert_eq!(mock.quest("1").unwrap().progress, flushed);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_video_quest_flush_leaves_out_paused_time() {
+        let mock = MockDiscord::start().await;
+        mock.add_quest(QuestFixture::new("1", "WATCH_VIDEO", 60).enrolled());
+        let events = RecordingEvents::new();
+        let (control_tx, mut control_rx) = tokio::sync::mpsc::channel(1);
+
+        let client = mock_client(&mock);
+        let run = complete_video_quest(&client, "1".into(), 60, 0.0, 1.0, 10, &events, &mut control_rx);
+        let pause_then_stop = async {
+            while events.events().is_empty() {
+                tokio::time::sleep(Duration::from_millis(1)).await;
+            }
+            tokio::time::sleep(Duration::from_secs(3)).await;
+            control_tx.send(QuestControl::Pause).await.unwrap();
+            tokio::time::sleep(Duration::from_secs(300)).await;
+            control_tx.send(QuestControl::Stop).await.unwrap();
+        };
+        let (result, _) = tokio::join!(run, pause_then_stop);
+        result.unwrap();
+
+        // Only the 3s watched before the pause count, not the 300s paused
+        let updates = mock.requests_to(Route::VideoProgress);
+        assert_eq!(updates.len(), 2);
+        let first = updates[0].body["timestamp"].as_f64().unwrap();
+        let flushed = updates[1].body["timestamp"].as_f64().unwrap();
+        assert!((flushed - first - 3.0).abs() < 0.1, "flushed {} after {}", flushed, first);
+        assert!(events.events().contains(&QuestEvent::Paused));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_video_quest_retries_transient_failure() {
+        let mock = MockDiscord::start().await;
+        mock.add_quest(QuestFixture::new("1", "WATCH_VIDEO", 30).enrolled());
+        mock.script(Route::VideoProgress, ScriptedResponse::ServerError(502));
+        mock.script(Route::VideoProgress, ScriptedResponse::ServerError(503));
+        let events = RecordingEvents::new();
+        let (_control_tx, mut control_rx) = tokio::sync::mpsc::channel(1);
+
+        complete_video_quest(&mock_client(&mock), "1".into(), 30, 0.0, 1.0, 10, &events, &mut control_rx)
+            .await
+            .unwrap();
+
+        let recorded = events.events();
+        let retries: Vec<_> = recorded
+            .iter()
+            .filter_map(|e| match e {
+                QuestEvent::Retrying(notice) => Some((notice.attempt, notice.delay_secs)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(retries, vec![(1, 2.0), (2, 4.0)]);
+        assert_eq!(recorded.last(), Some(&QuestEvent::Completed));
+        // The failed step is resent rather than skipped
+        assert_eq!(mock.requests_to(Route::VideoProgress).len(), 5);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_video_quest_follows_server_progress() {
+        let mock = MockDiscord::start().await;
+        mock.add_quest(QuestFixture::new("1", "WATCH_VIDEO", 30).enrolled());
+        // Discord only credits 4s of the first 10s step
+        mock.script(
+            Route::VideoProgress,
+            ScriptedResponse::Json(200, serde_json::json!({
+                "enrolled_at": "2025-01-01T00:00:00+00:00",
+                "completed_at": null,
+                "progress": { "WATCH_VIDEO": { "value": 4.0 } },
+            })),
+        );
+        let events = RecordingEvents::new();
+        let (_control_tx, mut control_rx) = tokio::sync::mpsc::channel(1);
+
+        complete_video_quest(&mock_client(&mock), "1".into(), 30, 0.0, 1.0, 10, &events, &mut control_rx)
+            .await
+            .unwrap();
+
+        let recorded = events.events();
+        assert!(matches!(recorded[0], QuestEvent::Progress(p) if (p - 4.0 / 30.0 * 100.0).abs() < 1e-9));
+        let second = mock.requests_to(Route::VideoProgress)[1].body["timestamp"].as_f64().unwrap();
+        assert!((14.0..14.5).contains(&second), "resumed from the server's 4s, sent {}", second);
+        assert_eq!(recorded.last(), Some(&QuestEvent::Completed));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_game_heartbeat_gives_up_when_server_progress_stalls() {
+        let mock = MockDiscord::start().await;
+        mock.add_quest(QuestFixture::new("1", "PLAY_ON_DESKTOP", 180).enrolled());
+        mock.set_heartbeat_increment(0.0);
+        let events = RecordingEvents::new();
+        let (_control_tx, mut control_rx) = tokio::sync::mpsc::channel(1);
+
+        let result =
+            complete_game_quest_via_heartbeat(&mock_client(&mock), "1".into(), "123".into(), 180, 0.0, &events, &mut control_rx)
+                .await;
+
+        // Three heartbeats would have been enough by the local count
+        assert!(result.is_err());
+        assert!(matches!(events.events().last(), Some(QuestEvent::Error(_))));
+        assert_eq!(mock.requests_to(Route::Heartbeat).len(), MAX_STALLED_STEPS as usize + 1);
+        assert!(!mock.quest("1").unwrap().completed);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_game_heartbeat_gives_up_after_consecutive_failures() {
+        let mock = MockDiscord::start().await;
+        mock.add_quest(QuestFixture::new("1", "PLAY_ON_DESKTOP", 180).enrolled());
+        for _ in 0..RetryPolicy::default().max_consecutive_failures {
+            mock.script(Route::Heartbeat, ScriptedResponse::ServerError(500));
+        }
+        let events = RecordingEvents::new();
+        let (_control_tx, mut control_rx) = tokio::sync::mpsc::channel(1);
+
+        let result =
+            complete_game_quest_via_heartbeat(&mock_client(&mock), "1".into(), "123".into(), 180, 0.0, &events, &mut control_rx)
+                .await;
+
+        assert!(result.is_err());
+        let recorded = events.events();
+        assert_eq!(recorded.iter().filter(|e| matches!(e, QuestEvent::Retrying(_))).count(), 4);
+        assert!(matches!(recorded.last(), Some(QuestEvent::Error(_))));
+        assert_eq!(mock.requests_to(Route::Heartbeat).len(), 5);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_game_heartbeat_quest_completes() {
+        let mock = MockDiscord::start().await;
+        mock.add_quest(QuestFixture::new("1", "PLAY_ON_DESKTOP", 180).enrolled());
+        let events = RecordingEvents::new();
+        let (_control_tx, mut control_rx) = tokio::sync::mpsc::channel(1);
+
+        complete_game_quest_via_heartbeat(&mock_client(&mock), "1".into(), "123".into(), 180, 0.0, &events, &mut control_rx)
+            .await
+            .unwrap();
+
+        // Three to play, then one to close the session
+        let heartbeats = mock.requests_to(Route::Heartbeat);
+        assert_eq!(heartbeats.len(), 4);
+        assert_eq!(heartbeats[2].body["terminal"], false);
+        assert_eq!(heartbeats[3].body["terminal"], true);
+        assert!(events.events().ends_with(&[QuestEvent::SessionClosed { acknowledged: true }, QuestEvent::Completed]));
+        assert!(mock.quest("1").unwrap().completed);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_game_heartbeat_sends_nothing_without_an_accepted_request() {
+        let mock = MockDiscord::start().await;
+        mock.add_quest(QuestFixture::new("1", "PLAY_ON_DESKTOP", 180).enrolled());
+        let client = mock_client(&mock);
+
+        // Already done on entry: no heartbeat at all, terminal or not
+        let events = RecordingEvents::new();
+        let (_control_tx, mut control_rx) = tokio::sync::mpsc::channel(1);
+        complete_game_quest_via_heartbeat(&client, "1".into(), "123".into(), 180, 100.0, &events, &mut control_rx)
+            .await
+            .unwrap();
+        assert!(mock.requests_to(Route::Heartbeat).is_empty());
+        assert_eq!(events.events().last(), Some(&QuestEvent::Completed));
+
+        // Stopped before the first heartbeat: nothing to close either
+        let events = RecordingEvents::new();
+        let (control_tx, mut control_rx) = tokio::sync::mpsc::channel(1);
+        control_tx.send(QuestControl::Stop).await.unwrap();
+        complete_game_quest_via_heartbeat(&client, "1".into(), "123".into(), 180, 0.0, &events, &mut control_rx)
+            .await
+            .unwrap();
+        assert!(mock.requests_to(Route::Heartbeat).is_empty());
+        assert!(!events.events().iter().any(|e| matches!(e, QuestEvent::SessionClosed { .. })));
+        assert_eq!(events.events().last(), Some(&QuestEvent::Stopped(None)));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_game_heartbeat_closes_session_on_cancel() {
+        let mock = MockDiscord::start().await;
+        mock.add_quest(QuestFixture::new("1", "PLAY_ON_DESKTOP", 900).enrolled());
+        // The first heartbeat goes through, the terminal one fails
+        mock.script(
+            Route::Heartbeat,
+            ScriptedResponse::Json(200, serde_json::json!({
+                "enrolled_at": "2025-01-01T00:00:00+00:00",
+                "completed_at": null,
+                "progress": { "PLAY_ON_DESKTOP": { "value": 60.0 } },
+            })),
+        );
+        mock.script(Route::Heartbeat, ScriptedResponse::ServerError(500));
+        let events = RecordingEvents::new();
+        let (control_tx, mut control_rx) = tokio::sync::mpsc::channel(1);
+
+        let client = mock_client(&mock);
+        let run = complete_game_quest_via_heartbeat(&client, "1".into(), "123".into(), 900, 0.0, &events, &mut control_rx);
+        let stop = async {
+            tokio::time::sleep(Duration::from_secs(30)).await;
+            control_tx.send(QuestControl::Stop).await.unwrap();
+        };
+        let (result, _) = tokio::join!(run, stop);
+        result.unwrap();
+
+        let heartbeats = mock.requests_to(Route::Heartbeat);
+        assert_eq!(heartbeats.len(), 2);
+        assert_eq!(heartbeats[1].body["terminal"], true);
+        assert!(events.events().ends_with(&[QuestEvent::SessionClosed { acknowledged: false }, QuestEvent::Stopped(None)]));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_stream_quest_stops_on_cancel() {
+        let mock = MockDiscord::start().await;
+        mock.add_quest(QuestFixture::new("1", "STREAM_ON_DESKTOP", 900).enrolled());
+        let events = RecordingEvents::new();
+        let (control_tx, mut control_rx) = tokio::sync::mpsc::channel(1);
+        control_tx.send(QuestControl::Stop).await.unwrap();
+
+        complete_stream_quest(&mock_client(&mock), "1".into(), "key".into(), 900, 0.0, &events, &mut control_rx)
+            .await
+            .unwrap();
+
+        assert!(mock.requests_to(Route::Heartbeat).is_empty());
+        assert_eq!(events.events(), vec![QuestEvent::Stopped(None)]);
+    }
+
+    /// Counts to four without touching the network
+    struct CountingStrategy {
+        steps: u32,
+    }
+
+    impl QuestStrategy for CountingStrategy {
+        type Response = u32;
+
+        fn name(&self) -> &'static str {
+            "Counting"
+        }
+
+        async fn next_request(&self, _client: &DiscordApiClient) -> crate::error::ApiResult<u32> {
+            Ok(self.steps + 1)
+        }
+
+        fn interval(&self) -> Duration {
+            Duration::from_secs(5)
+        }
+
+        fn interpret(&mut self, steps: u32) -> f64 {
+            self.steps = steps;
+            steps as f64 * 25.0
+        }
+
+        fn is_done(&self) -> bool {
+            self.steps >= 4
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_custom_strategy_runs_through_driver() {
+        let client = DiscordApiClient::new("token", DiscordEndpoints::local("http://127.0.0.1:9", "ws://127.0.0.1:9")).unwrap();
+        let events = RecordingEvents::new();
+        let (_control_tx, mut control_rx) = tokio::sync::mpsc::channel(1);
+
+        let started = tokio::time::Instant::now();
+        run_strategy(&client, &mut CountingStrategy { steps: 0 }, &events, &mut control_rx).await.unwrap();
+
+        assert_eq!(
+            events.events(),
+            vec![
+                QuestEvent::Progress(25.0),
+                QuestEvent::Progress(50.0),
+                QuestEvent::Progress(75.0),
+                QuestEvent::Progress(100.0),
+                QuestEvent::Completed,
+            ]
+        );
+        // No wait after the last step
+        assert_eq!(started.elapsed(), Duration::from_secs(15));
+    }
+
+    #[test]
+    fn test_generate_stream_key() {
+        let key1 = generate_stream_key();
+        let key2 = generate_stream_key();
+        
+        assert!(key1.starts_with("stream_"));
+        assert!(key2.starts_with("stream_"));
+        assert_ne!(key1, key2);
+        assert_eq!(key1.len(), 39); // "stream_" + 32 chars
+    }
+}
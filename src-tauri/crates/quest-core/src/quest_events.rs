@@ -0,0 +1,165 @@
+use crate::jobs::QuestJob;
+use crate::retry::RetryNotice;
+use crate::verify::VerificationReport;
+use std::sync::atomic::{AtomicBool, Ordering};
+#[cfg(test)]
+use std::sync::{Arc, Mutex};
+use tokio::sync::mpsc;
+
+/// Sink for quest progress reported by the completers
+///
+/// Implementors only provide [`emit`](QuestEvents::emit); the named methods
+/// are what the completers call, and each wraps its [`QuestEvent`]. Wrappers
+/// forward whatever they don't act on, so a new event needs no changes there.
+///
+/// Implemented by the Tauri shell (frontend events), by [`ChannelEvents`]
+/// (headless consumers) and by `RecordingEvents` (tests).
+pub trait QuestEvents: Send + Sync {
+    fn emit(&self, event: QuestEvent);
+
+    /// A run of `job` is starting; the events that follow belong to it
+    fn started(&self, job: &QuestJob) {
+        self.emit(QuestEvent::Started(job.clone()));
+    }
+    /// Progress percentage (0-100)
+    fn progress(&self, progress: f64) {
+        self.emit(QuestEvent::Progress(progress));
+    }
+    /// Quest reached its target
+    fn completed(&self) {
+        self.emit(QuestEvent::Completed);
+    }
+    /// Quest was cancelled; `flushed_progress` is the percentage Discord
+    /// confirmed for the final position saved on the way out, if any
+    fn stopped(&self, flushed_progress: Option<f64>) {
+        self.emit(QuestEvent::Stopped(flushed_progress));
+    }
+    /// Quest aborted with an error
+    fn error(&self, message: &str) {
+        self.emit(QuestEvent::Error(message.to_string()));
+    }
+    /// A request failed transiently and will be retried
+    fn retrying(&self, notice: &RetryNotice) {
+        self.emit(QuestEvent::Retrying(notice.clone()));
+    }
+    /// Quest was paused; its position is kept
+    fn paused(&self) {
+        self.emit(QuestEvent::Paused);
+    }
+    /// Paused quest continues
+    fn resumed(&self) {
+        self.emit(QuestEvent::Resumed);
+    }
+    /// Discord confirmed the completion on a refetch
+    fn verified(&self, report: &VerificationReport) {
+        self.emit(QuestEvent::Verified(report.clone()));
+    }
+    /// The quest was reported complete but Discord didn't record it
+    fn verification_failed(&self, report: &VerificationReport) {
+        self.emit(QuestEvent::VerificationFailed(report.clone()));
+    }
+    /// A terminal heartbeat closed the session; `acknowledged` is whether Discord accepted it
+    fn session_closed(&self, acknowledged: bool) {
+        self.emit(QuestEvent::SessionClosed { acknowledged });
+    }
+}
+
+/// A single quest event
+#[derive(Debug, Clone, PartialEq)]
+pub enum QuestEvent {
+    Started(QuestJob),
+    Progress(f64),
+    Completed,
+    Stopped(Option<f64>),
+    Error(String),
+    Retrying(RetryNotice),
+    Paused,
+    Resumed,
+    Verified(VerificationReport),
+    VerificationFailed(VerificationReport),
+    SessionClosed { acknowledged: bool },
+}
+
+/// Forwards events into an unbounded channel
+#[derive(Clone)]
+pub struct ChannelEvents {
+    tx: mpsc::UnboundedSender<QuestEvent>,
+}
+
+impl ChannelEvents {
+    pub fn new() -> (Self, mpsc::UnboundedReceiver<QuestEvent>) {
+        let (tx, rx) = mpsc::unbounded_channel();
+        (Self { tx }, rx)
+    }
+}
+
+impl QuestEvents for ChannelEvents {
+    fn emit(&self, event: QuestEvent) {
+        let _ = self.tx.send(event);
+    }
+}
+
+/// Records every event in memory
+#[cfg(test)]
+#[derive(Clone, Default)]
+pub struct RecordingEvents {
+    events: Arc<Mutex<Vec<QuestEvent>>>,
+}
+
+#[cfg(test)]
+impl RecordingEvents {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Snapshot of the events recorded so far
+    pub fn events(&self) -> Vec<QuestEvent> {
+        self.events.lock().unwrap().clone()
+    }
+}
+
+#[cfg(test)]
+impl QuestEvents for RecordingEvents {
+    fn emit(&self, event: QuestEvent) {
+        self.events.lock().unwrap().push(event);
+    }
+}
+
+/// Forwards quest events and remembers whether the quest reached its target
+pub struct OutcomeEvents<'a, E: QuestEvents + ?Sized> {
+    inner: &'a E,
+    completed: AtomicBool,
+}
+
+impl<'a, E: QuestEvents + ?Sized> OutcomeEvents<'a, E> {
+    pub fn new(inner: &'a E) -> Self {
+        Self {
+            inner,
+            completed: AtomicBool::new(false),
+        }
+    }
+
+    /// Whether `completed` was emitted (as opposed to stopped or failed)
+    pub fn reached_target(&self) -> bool {
+        self.completed.load(Ordering::Relaxed)
+    }
+}
+
+impl<E: QuestEvents + ?Sized> QuestEvents for OutcomeEvents<'_, E> {
+    fn emit(&self, event: QuestEvent) {
+        if event == QuestEvent::Completed {
+            self.completed.store(true, Ordering::Relaxed);
+        }
+        self.inner.emit(event);
+    }
+}
+
+/// Borrowed sinks forward to the sink they point at
+///
+/// Lets wrappers that own their inner sink, like [`crate::jobs::PersistingEvents`],
+/// wrap one that is only borrowed.
+impl<E: QuestEvents + ?Sized> QuestEvents for &E {
+    fn emit(&self, event: QuestEvent) {
+        (**self).emit(event);
+    }
+}
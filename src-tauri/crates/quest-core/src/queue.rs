@@ -0,0 +1,405 @@
+use crate::discord_api::DiscordApiClient;
+use crate::dispatch::{self, Prepared, RunOptions};
+use crate::jobs::{JobStore, PersistingEvents};
+use crate::quest_events::{OutcomeEvents, QuestEvents};
+use crate::registry::{QuestRegistry, RegisteredEvents};
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::time::sleep;
+use tracing::{info, warn};
+
+/// Pause between two queue items
+const ITEM_GAP: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QueueItemStatus {
+    Pending,
+    Running,
+    Completed,
+    Failed,
+    /// Cancelled by the user (the queue moves on unless it was stopped too)
+    Stopped,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct QueueItem {
+    pub quest_id: String,
+    pub status: QueueItemStatus,
+    /// Why the item failed
+    pub error: Option<String>,
+}
+
+/// Queue contents as shown to the UI
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct QueueSnapshot {
+    pub items: Vec<QueueItem>,
+    pub running: bool,
+    /// Quest currently being run, if any
+    pub current: Option<String>,
+}
+
+/// Sink for queue-level events, next to the per-quest [`QuestEvents`]
+pub trait QueueEvents: Send + Sync {
+    /// Items were added, removed, reordered or changed status
+    fn changed(&self, snapshot: &QueueSnapshot);
+    /// An item started running
+    fn item_started(&self, quest_id: &str);
+    /// An item completed, failed or was stopped
+    fn item_finished(&self, item: &QueueItem);
+    /// Queue ran out of pending items or was stopped
+    fn finished(&self, snapshot: &QueueSnapshot);
+}
+
+#[derive(Default)]
+struct QueueState {
+    items: Vec<QueueItem>,
+    running: bool,
+    stop_requested: bool,
+}
+
+impl QueueState {
+    fn snapshot(&self) -> QueueSnapshot {
+        QueueSnapshot {
+            items: self.items.clone(),
+            running: self.running,
+            current: self
+                .items
+                .iter()
+                .find(|i| i.status == QueueItemStatus::Running)
+                .map(|i| i.quest_id.clone()),
+        }
+    }
+}
+
+/// Ordered list of quests to run one after another
+///
+/// Editing (`enqueue`, `reorder`, `remove`) is allowed while [`run_queue`] is
+/// working through it; the runner always picks the first pending item.
+#[derive(Default)]
+pub struct QuestQueue {
+    state: Mutex<QueueState>,
+}
+
+impl QuestQueue {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn snapshot(&self) -> QueueSnapshot {
+        self.state.lock().unwrap().snapshot()
+    }
+
+    /// Append quests; ids already pending or running are skipped, finished ones are queued again
+    pub fn enqueue(&self, quest_ids: &[String]) -> QueueSnapshot {
+        let mut state = self.state.lock().unwrap();
+        for quest_id in quest_ids {
+            let existing = state.items.iter().position(|i| &i.quest_id == quest_id);
+            match existing {
+                Some(index) if matches!(state.items[index].status, QueueItemStatus::Pending | QueueItemStatus::Running) => {}
+                Some(index) => {
+                    let mut item = state.items.remove(index);
+                    item.status = QueueItemStatus::Pending;
+                    item.error = None;
+                    state.items.push(item);
+                }
+                None => state.items.push(QueueItem {
+                    quest_id: quest_id.clone(),
+                    status: QueueItemStatus::Pending,
+                    error: None,
+                }),
+            }
+        }
+        state.snapshot()
+    }
+
+    /// Move the listed quests to the front, in that order; unlisted items keep their relative order
+    pub fn reorder(&self, quest_ids: &[String]) -> QueueSnapshot {
+        let mut state = self.state.lock().unwrap();
+        let mut rest = std::mem::take(&mut state.items);
+        let mut items = Vec::with_capacity(rest.len());
+        for quest_id in quest_ids {
+            if let Some(index) = rest.iter().position(|i| &i.quest_id == quest_id) {
+                items.push(rest.remove(index));
+            }
+        }
+        items.append(&mut rest);
+        state.items = items;
+        state.snapshot()
+    }
+
+    /// Drop an item; the running item can't be removed (stop it instead)
+    pub fn remove(&self, quest_id: &str) -> Option<QueueSnapshot> {
+        let mut state = self.state.lock().unwrap();
+        let index = state
+            .items
+            .iter()
+            .position(|i| i.quest_id == quest_id && i.status != QueueItemStatus::Running)?;
+        state.items.remove(index);
+        Some(state.snapshot())
+    }
+
+    /// Mark the queue as running; false if it already is
+    ///
+    /// Must succeed before calling [`run_queue`].
+    pub fn try_start(&self) -> bool {
+        let mut state = self.state.lock().unwrap();
+        if state.running {
+            return false;
+        }
+        state.running = true;
+        state.stop_requested = false;
+        true
+    }
+
+    /// Ask the runner to stop after the current item; returns the running quest id
+    ///
+    /// The caller is expected to cancel that quest through the [`QuestRegistry`].
+    pub fn request_stop(&self) -> Option<String> {
+        let mut state = self.state.lock().unwrap();
+        if !state.running {
+            return None;
+        }
+        state.stop_requested = true;
+        state.snapshot().current
+    }
+
+    /// Claim the first pending item, or end the run if there is none (or a stop was requested)
+    fn next_item(&self) -> Option<String> {
+        let mut state = self.state.lock().unwrap();
+        let next = if state.stop_requested {
+            None
+        } else {
+            state.items.iter_mut().find(|i| i.status == QueueItemStatus::Pending)
+        };
+
+        match next {
+            Some(item) => {
+                item.status = QueueItemStatus::Running;
+                Some(item.quest_id.clone())
+            }
+            None => {
+                state.running = false;
+                state.stop_requested = false;
+                None
+            }
+        }
+    }
+
+    fn finish_item(&self, quest_id: &str, status: QueueItemStatus, error: Option<String>) -> QueueItem {
+        let mut state = self.state.lock().unwrap();
+        let item = QueueItem {
+            quest_id: quest_id.to_string(),
+            status,
+            error,
+        };
+        if let Some(existing) = state.items.iter_mut().find(|i| i.quest_id == quest_id) {
+            *existing = item.clone();
+        }
+        item
+    }
+}
+
+/// Run one queued quest with the completer its task config calls for
+///
+/// Like a quest started on its own, the job is kept in `jobs` while it runs so
+/// it can be resumed after a crash.
+async fn run_item<E: QuestEvents + ?Sized>(
+    client: &DiscordApiClient,
+    registry: &QuestRegistry,
+    jobs: &Arc<JobStore>,
+    options: &RunOptions,
+    quest_id: &str,
+    events: &E,
+) -> anyhow::Result<QueueItemStatus> {
+    let job = match dispatch::prepare_quest(client, quest_id, options).await? {
+        Prepared::AlreadyCompleted => return Ok(QueueItemStatus::Completed),
+        Prepared::Run(job) => job,
+    };
+
+    let run = jobs.save(job.clone());
+    let (ticket, control_rx) = registry.start(quest_id, job.task.kind());
+    let persisting = PersistingEvents::new(events, jobs.clone(), quest_id.to_string(), run);
+    let registered = RegisteredEvents::new(&persisting, registry, quest_id, ticket);
+    let tracker = OutcomeEvents::new(&registered);
+    let result = dispatch::run_job(client, &job, &tracker, control_rx).await;
+    registry.finish(quest_id, ticket);
+
+    result?;
+    Ok(if tracker.reached_target() {
+        QueueItemStatus::Completed
+    } else {
+        QueueItemStatus::Stopped
+    })
+}
+
+/// Work through the queue until no pending item is left or a stop is requested
+///
+/// A failed item is marked as such and the queue moves on to the next one.
+/// Only call this after [`QuestQueue::try_start`] returned true.
+pub async fn run_queue<E, Q>(
+    queue: &QuestQueue,
+    client: &DiscordApiClient,
+    registry: &QuestRegistry,
+    jobs: &Arc<JobStore>,
+    options: &RunOptions,
+    events: &E,
+    queue_events: &Q,
+) where
+    E: QuestEvents + ?Sized,
+    Q: QueueEvents + ?Sized,
+{
+    let mut first = true;
+    loop {
+        if !first {
+            sleep(ITEM_GAP).await;
+        }
+        first = false;
+
+        let Some(quest_id) = queue.next_item() else {
+            break;
+        };
+
+        info!("Queue: starting quest {}", quest_id);
+        queue_events.item_started(&quest_id);
+        queue_events.changed(&queue.snapshot());
+
+        let (status, error) = match run_item(client, registry, jobs, options, &quest_id, events).await {
+            Ok(status) => (status, None),
+            Err(e) => {
+                warn!("Queue: quest {} failed: {}", quest_id, e);
+                (QueueItemStatus::Failed, Some(e.to_string()))
+            }
+        };
+
+        let item = queue.finish_item(&quest_id, status, error);
+        queue_events.item_finished(&item);
+        queue_events.changed(&queue.snapshot());
+    }
+
+    let snapshot = queue.snapshot();
+    info!("Queue finished");
+    queue_events.changed(&snapshot);
+    queue_events.finished(&snapshot);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::quest_events::RecordingEvents;
+    use crate::test_support::mock_client;
+    use mock_discord::{MockDiscord, QuestFixture};
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    #[derive(Default)]
+    struct RecordingQueueEvents {
+        finished_items: Mutex<Vec<QueueItem>>,
+        finished: AtomicBool,
+    }
+
+    impl QueueEvents for RecordingQueueEvents {
+        fn changed(&self, _snapshot: &QueueSnapshot) {}
+
+        fn item_started(&self, _quest_id: &str) {}
+
+        fn item_finished(&self, item: &QueueItem) {
+            self.finished_items.lock().unwrap().push(item.clone());
+        }
+
+        fn finished(&self, _snapshot: &QueueSnapshot) {
+            self.finished.store(true, Ordering::Relaxed);
+        }
+    }
+
+    fn ids(snapshot: &QueueSnapshot) -> Vec<&str> {
+        snapshot.items.iter().map(|i| i.quest_id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_enqueue_reorder_remove() {
+        let queue = QuestQueue::new();
+        queue.enqueue(&["1".into(), "2".into(), "3".into(), "1".into()]);
+
+        let snapshot = queue.reorder(&["3".into(), "1".into()]);
+        assert_eq!(ids(&snapshot), vec!["3", "1", "2"]);
+
+        let snapshot = queue.remove("1").unwrap();
+        assert_eq!(ids(&snapshot), vec!["3", "2"]);
+        assert!(queue.remove("1").is_none());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_queue_runs_past_failed_item() {
+        let mock = MockDiscord::start().await;
+        mock.add_quest(QuestFixture::new("1", "WATCH_VIDEO", 30).enrolled());
+        mock.add_quest(QuestFixture::new("2", "PLAY_ON_XBOX", 30));
+        mock.add_quest(QuestFixture::new("3", "PLAY_ON_DESKTOP", 120).enrolled());
+        let client = mock_client(&mock);
+
+        let queue = QuestQueue::new();
+        queue.enqueue(&["1".into(), "2".into(), "3".into()]);
+        assert!(queue.try_start());
+        assert!(!queue.try_start());
+
+        let registry = QuestRegistry::new();
+        let queue_events = RecordingQueueEvents::default();
+        let options = RunOptions { heartbeat_interval: 10, ..Default::default() };
+        let jobs = Arc::new(JobStore::in_memory());
+        run_queue(&queue, &client, &registry, &jobs, &options, &RecordingEvents::new(), &queue_events).await;
+
+        let statuses: Vec<_> = queue_events
+            .finished_items
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|i| (i.quest_id.clone(), i.status))
+            .collect();
+        assert_eq!(
+            statuses,
+            vec![
+                ("1".to_string(), QueueItemStatus::Completed),
+                ("2".to_string(), QueueItemStatus::Failed),
+                ("3".to_string(), QueueItemStatus::Completed),
+            ]
+        );
+        assert!(queue_events.finished.load(Ordering::Relaxed));
+        assert!(!queue.snapshot().running);
+        assert!(mock.quest("3").unwrap().completed);
+        // Completed items leave no job behind; item 2 failed before it had one
+        assert!(jobs.list().is_empty());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_queue_items_are_kept_in_job_store_while_running() {
+        let mock = MockDiscord::start().await;
+        mock.add_quest(QuestFixture::new("1", "WATCH_VIDEO", 30).enrolled());
+        let client = mock_client(&mock);
+
+        let queue = QuestQueue::new();
+        queue.enqueue(&["1".into()]);
+        assert!(queue.try_start());
+
+        let registry = QuestRegistry::new();
+        let jobs = Arc::new(JobStore::in_memory());
+        let options = RunOptions { heartbeat_interval: 10, ..Default::default() };
+        let events = RecordingEvents::new();
+        let queue_events = RecordingQueueEvents::default();
+        let run = run_queue(&queue, &client, &registry, &jobs, &options, &events, &queue_events);
+        let watch = async {
+            for _ in 0..600 {
+                if let Some(job) = jobs.get("1") {
+                    return Some(job);
+                }
+                sleep(Duration::from_millis(100)).await;
+            }
+            None
+        };
+        let ((), job) = tokio::join!(run, watch);
+
+        let job = job.expect("job was never saved while the item ran");
+        assert_eq!(job.task.kind(), "video");
+        assert!(mock.quest("1").unwrap().completed);
+        assert!(jobs.get("1").is_none());
+    }
+}
@@ -0,0 +1,161 @@
+use reqwest::header::HeaderMap;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::time::{sleep, Instant};
+use tracing::info;
+
+/// Longest 429 wait the client absorbs on its own; longer ones are returned as errors
+pub const MAX_AUTO_RETRY_AFTER: Duration = Duration::from_secs(60);
+/// How many 429s in a row a single call retries before giving up
+pub const MAX_RATE_LIMIT_RETRIES: u32 = 3;
+/// Cap on any wait taken from a header or response body
+pub const MAX_SERVER_DELAY: Duration = Duration::from_secs(60 * 60);
+
+/// Per-route rate limit state, fed from `X-RateLimit-*` headers and 429 responses
+///
+/// Routes are keyed by method and path (e.g. `POST /api/v9/quests/1/heartbeat`),
+/// so one throttled quest doesn't delay requests for another.
+#[derive(Debug, Default)]
+pub struct RateLimiter {
+    state: Mutex<LimiterState>,
+}
+
+#[derive(Debug, Default)]
+struct LimiterState {
+    buckets: HashMap<String, Bucket>,
+    global_reset: Option<Instant>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Bucket {
+    remaining: u32,
+    reset_at: Instant,
+}
+
+/// Numeric header value; `inf` and `NaN` parse as `f64` but are ignored like any other garbage
+fn header_f64(headers: &HeaderMap, name: &str) -> Option<f64> {
+    let value: f64 = headers.get(name)?.to_str().ok()?.trim().parse().ok()?;
+    value.is_finite().then_some(value)
+}
+
+/// Wait of `secs` seconds as sent by Discord, without trusting it to be sane
+///
+/// Negative values wait for nothing; NaN, infinite and overly large ones are
+/// capped at [`MAX_SERVER_DELAY`] instead of panicking in `Duration`.
+pub fn server_delay(secs: f64) -> Duration {
+    if secs.is_nan() {
+        return MAX_SERVER_DELAY;
+    }
+    Duration::try_from_secs_f64(secs.max(0.0)).map_or(MAX_SERVER_DELAY, |d| d.min(MAX_SERVER_DELAY))
+}
+
+impl RateLimiter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// How long `route` has to wait before the next request, if at all
+    pub fn delay_for(&self, route: &str) -> Option<Duration> {
+        let now = Instant::now();
+        let state = self.state.lock().unwrap();
+
+        let global = state.global_reset.filter(|reset| *reset > now);
+        let bucket = state
+            .buckets
+            .get(route)
+            .filter(|b| b.remaining == 0 && b.reset_at > now)
+            .map(|b| b.reset_at);
+
+        global.max(bucket).map(|until| until - now)
+    }
+
+    /// Sleep until `route` may be called again
+    pub async fn wait(&self, route: &str) {
+        if let Some(delay) = self.delay_for(route) {
+            info!("Rate limit: waiting {:.2}s before {}", delay.as_secs_f64(), route);
+            sleep(delay).await;
+        }
+    }
+
+    /// Record the `X-RateLimit-Remaining` / `X-RateLimit-Reset-After` headers of a response
+    pub fn update(&self, route: &str, headers: &HeaderMap) {
+        let remaining = header_f64(headers, "x-ratelimit-remaining");
+        let reset_after = header_f64(headers, "x-ratelimit-reset-after");
+
+        if let (Some(remaining), Some(reset_after)) = (remaining, reset_after) {
+            let bucket = Bucket {
+                remaining: remaining.max(0.0) as u32,
+                reset_at: Instant::now() + server_delay(reset_after),
+            };
+            self.state.lock().unwrap().buckets.insert(route.to_string(), bucket);
+        }
+    }
+
+    /// Record a 429; a global limit blocks every route
+    pub fn limited(&self, route: &str, retry_after: f64, global: bool) {
+        let reset_at = Instant::now() + server_delay(retry_after);
+        let mut state = self.state.lock().unwrap();
+
+        if global {
+            state.global_reset = Some(reset_at);
+        } else {
+            state.buckets.insert(
+                route.to_string(),
+                Bucket {
+                    remaining: 0,
+                    reset_at,
+                },
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    #[tokio::test(start_paused = true)]
+    async fn test_exhausted_bucket_delays_route() {
+        let limiter = RateLimiter::new();
+        let mut headers = HeaderMap::new();
+        headers.insert("x-ratelimit-remaining", HeaderValue::from_static("0"));
+        headers.insert("x-ratelimit-reset-after", HeaderValue::from_static("2.5"));
+
+        limiter.update("POST /a", &headers);
+
+        assert_eq!(limiter.delay_for("POST /a"), Some(Duration::from_millis(2500)));
+        assert_eq!(limiter.delay_for("POST /b"), None);
+
+        tokio::time::advance(Duration::from_secs(3)).await;
+        assert_eq!(limiter.delay_for("POST /a"), None);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_global_limit_blocks_every_route() {
+        let limiter = RateLimiter::new();
+        limiter.limited("POST /a", 1.0, true);
+
+        assert_eq!(limiter.delay_for("GET /b"), Some(Duration::from_secs(1)));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_malformed_server_delays_do_not_panic() {
+        let limiter = RateLimiter::new();
+        let mut headers = HeaderMap::new();
+        headers.insert("x-ratelimit-remaining", HeaderValue::from_static("0"));
+        headers.insert("x-ratelimit-reset-after", HeaderValue::from_static("inf"));
+        limiter.update("POST /a", &headers);
+        assert_eq!(limiter.delay_for("POST /a"), None);
+
+        headers.insert("x-ratelimit-reset-after", HeaderValue::from_static("1e20"));
+        limiter.update("POST /a", &headers);
+        assert_eq!(limiter.delay_for("POST /a"), Some(MAX_SERVER_DELAY));
+
+        limiter.limited("POST /b", f64::NAN, false);
+        assert_eq!(limiter.delay_for("POST /b"), Some(MAX_SERVER_DELAY));
+        assert_eq!(server_delay(-3.0), Duration::ZERO);
+        assert_eq!(server_delay(f64::INFINITY), MAX_SERVER_DELAY);
+    }
+}
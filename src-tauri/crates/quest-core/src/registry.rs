@@ -0,0 +1,318 @@
+use crate::quest_completer::QuestControl;
+use crate::quest_events::{QuestEvent, QuestEvents};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::sync::{mpsc, Notify};
+use tracing::info;
+
+/// Public view of a running quest, as returned by `list_running_quests`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RunningQuestInfo {
+    pub quest_id: String,
+    /// Completer driving the quest (`video`, `stream` or `game`)
+    pub kind: String,
+    /// Unix timestamp (seconds) of when the run started
+    pub started_at: u64,
+    pub paused: bool,
+    /// Latest progress percentage confirmed by Discord
+    pub progress: f64,
+    /// Target of the task being completed (0 until the run has started)
+    pub seconds_needed: u32,
+}
+
+/// A quest task registered in [`QuestRegistry`]
+pub struct RunningQuest {
+    pub info: RunningQuestInfo,
+    /// Distinguishes this run from a later restart of the same quest
+    run_id: u64,
+    control_tx: mpsc::Sender<QuestControl>,
+}
+
+/// Ticket handed to a started quest task, used to deregister it when it ends
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RunTicket(u64);
+
+/// Quest tasks that are currently running, keyed by quest id
+///
+/// Different quests run side by side; starting a quest that is already
+/// running cancels the previous run first.
+#[derive(Default)]
+pub struct QuestRegistry {
+    quests: Mutex<HashMap<String, RunningQuest>>,
+    next_run_id: AtomicU64,
+    /// Runs started but not finished yet, including stopped ones still winding down
+    live_runs: AtomicUsize,
+    all_finished: Notify,
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+impl QuestRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a new run of `quest_id` and return its ticket and control receiver
+    pub fn start(&self, quest_id: &str, kind: &str) -> (RunTicket, mpsc::Receiver<QuestControl>) {
+        let (control_tx, control_rx) = mpsc::channel(8);
+        let run_id = self.next_run_id.fetch_add(1, Ordering::Relaxed);
+        self.live_runs.fetch_add(1, Ordering::SeqCst);
+
+        let previous = self.quests.lock().unwrap().insert(
+            quest_id.to_string(),
+            RunningQuest {
+                info: RunningQuestInfo {
+                    quest_id: quest_id.to_string(),
+                    kind: kind.to_string(),
+                    started_at: unix_now(),
+                    paused: false,
+                    progress: 0.0,
+                    seconds_needed: 0,
+                },
+                run_id,
+                control_tx,
+            },
+        );
+
+        if let Some(previous) = previous {
+            info!("Quest {} was already running, replacing it", quest_id);
+            let _ = previous.control_tx.try_send(QuestControl::Stop);
+        }
+
+        (RunTicket(run_id), control_rx)
+    }
+
+    /// Deregister a run once its task has ended
+    ///
+    /// Leaves the entry alone if the quest has since been restarted under a new ticket.
+    pub fn finish(&self, quest_id: &str, ticket: RunTicket) {
+        {
+            let mut quests = self.quests.lock().unwrap();
+            if quests.get(quest_id).is_some_and(|q| q.run_id == ticket.0) {
+                quests.remove(quest_id);
+            }
+        }
+        if self.live_runs.fetch_sub(1, Ordering::SeqCst) == 1 {
+            self.all_finished.notify_waiters();
+        }
+    }
+
+    /// Wait until every started run has called [`finish`](Self::finish)
+    ///
+    /// Stopped runs count until their task has actually ended, so this is how
+    /// shutdown waits for them to close their sessions.
+    pub async fn wait_finished(&self) {
+        loop {
+            let notified = self.all_finished.notified();
+            if self.live_runs.load(Ordering::SeqCst) == 0 {
+                return;
+            }
+            notified.await;
+        }
+    }
+
+    /// Cancel one quest; returns false if it wasn't running
+    ///
+    /// Removing the entry drops the sender, so the task stops even if the
+    /// control channel happens to be full.
+    pub fn stop(&self, quest_id: &str) -> bool {
+        let quest = self.quests.lock().unwrap().remove(quest_id);
+        match quest {
+            Some(quest) => {
+                let _ = quest.control_tx.try_send(QuestControl::Stop);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Pause a running quest; returns false if it isn't running or already paused
+    pub fn pause(&self, quest_id: &str) -> bool {
+        self.set_paused(quest_id, true)
+    }
+
+    /// Resume a paused quest; returns false if it isn't running or not paused
+    pub fn resume(&self, quest_id: &str) -> bool {
+        self.set_paused(quest_id, false)
+    }
+
+    fn set_paused(&self, quest_id: &str, paused: bool) -> bool {
+        let mut quests = self.quests.lock().unwrap();
+        let Some(quest) = quests.get_mut(quest_id).filter(|q| q.info.paused != paused) else {
+            return false;
+        };
+
+        let control = if paused { QuestControl::Pause } else { QuestControl::Resume };
+        if quest.control_tx.try_send(control).is_err() {
+            return false;
+        }
+        quest.info.paused = paused;
+        true
+    }
+
+    /// Cancel every running quest; returns how many were stopped
+    pub fn stop_all(&self) -> usize {
+        let quests: Vec<_> = self.quests.lock().unwrap().drain().collect();
+        for (_, quest) in &quests {
+            let _ = quest.control_tx.try_send(QuestControl::Stop);
+        }
+        quests.len()
+    }
+
+    /// Record the latest progress of a run; ignored once it was replaced or stopped
+    pub fn record_progress(&self, quest_id: &str, ticket: RunTicket, progress: f64) {
+        self.update_info(quest_id, ticket, |info| info.progress = progress);
+    }
+
+    fn update_info(&self, quest_id: &str, ticket: RunTicket, update: impl FnOnce(&mut RunningQuestInfo)) {
+        let mut quests = self.quests.lock().unwrap();
+        if let Some(quest) = quests.get_mut(quest_id).filter(|q| q.run_id == ticket.0) {
+            update(&mut quest.info);
+        }
+    }
+
+    pub fn is_running(&self, quest_id: &str) -> bool {
+        self.quests.lock().unwrap().contains_key(quest_id)
+    }
+
+    /// Running quests, oldest first
+    pub fn list(&self) -> Vec<RunningQuestInfo> {
+        let quests = self.quests.lock().unwrap();
+        let mut running: Vec<_> = quests.values().map(|q| (q.run_id, q.info.clone())).collect();
+        running.sort_by_key(|(run_id, _)| *run_id);
+        running.into_iter().map(|(_, info)| info).collect()
+    }
+}
+
+/// Forwards quest events and keeps the run's progress in a [`QuestRegistry`] current
+pub struct RegisteredEvents<'a, E: QuestEvents + ?Sized> {
+    inner: &'a E,
+    registry: &'a QuestRegistry,
+    quest_id: String,
+    ticket: RunTicket,
+}
+
+impl<'a, E: QuestEvents + ?Sized> RegisteredEvents<'a, E> {
+    /// `ticket` is what [`QuestRegistry::start`] returned for this run
+    pub fn new(inner: &'a E, registry: &'a QuestRegistry, quest_id: &str, ticket: RunTicket) -> Self {
+        Self {
+            inner,
+            registry,
+            quest_id: quest_id.to_string(),
+            ticket,
+        }
+    }
+}
+
+impl<E: QuestEvents + ?Sized> QuestEvents for RegisteredEvents<'_, E> {
+    fn emit(&self, event: QuestEvent) {
+        match &event {
+            QuestEvent::Started(job) => self.registry.update_info(&self.quest_id, self.ticket, |info| {
+                info.progress = job.progress;
+                info.seconds_needed = job.seconds_needed;
+            }),
+            QuestEvent::Progress(progress) => self.registry.record_progress(&self.quest_id, self.ticket, *progress),
+            _ => {}
+        }
+        self.inner.emit(event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quests_run_side_by_side() {
+        let registry = QuestRegistry::new();
+        let (_, mut video_rx) = registry.start("1", "video");
+        let (_, mut game_rx) = registry.start("2", "game");
+
+        let ids: Vec<_> = registry.list().into_iter().map(|q| q.quest_id).collect();
+        assert_eq!(ids, vec!["1", "2"]);
+
+        assert!(registry.stop("1"));
+        assert_eq!(video_rx.try_recv(), Ok(QuestControl::Stop));
+        assert!(game_rx.try_recv().is_err());
+        assert!(!registry.stop("1"));
+
+        assert_eq!(registry.stop_all(), 1);
+        assert_eq!(game_rx.try_recv(), Ok(QuestControl::Stop));
+        assert!(registry.list().is_empty());
+    }
+
+    #[test]
+    fn test_pause_and_resume() {
+        let registry = QuestRegistry::new();
+        let (_, mut rx) = registry.start("1", "video");
+
+        assert!(registry.pause("1"));
+        assert!(!registry.pause("1"));
+        assert!(registry.list()[0].paused);
+        assert!(registry.resume("1"));
+        assert!(!registry.resume("1"));
+        assert!(!registry.pause("2"));
+
+        assert_eq!(rx.try_recv(), Ok(QuestControl::Pause));
+        assert_eq!(rx.try_recv(), Ok(QuestControl::Resume));
+    }
+
+    #[test]
+    fn test_restart_cancels_previous_run() {
+        let registry = QuestRegistry::new();
+        let (old_ticket, mut old_rx) = registry.start("1", "video");
+        let (new_ticket, _new_rx) = registry.start("1", "video");
+
+        assert!(old_rx.try_recv().is_ok());
+
+        // The old task ending must not deregister the new run
+        registry.finish("1", old_ticket);
+        assert!(registry.is_running("1"));
+
+        registry.finish("1", new_ticket);
+        assert!(!registry.is_running("1"));
+    }
+
+    #[test]
+    fn test_registered_events_track_progress() {
+        let registry = QuestRegistry::new();
+        let (ticket, _rx) = registry.start("1", "video");
+        let recording = crate::quest_events::RecordingEvents::new();
+        let events = RegisteredEvents::new(&recording, &registry, "1", ticket);
+
+        events.progress(40.0);
+        assert_eq!(registry.list()[0].progress, 40.0);
+
+        // A late event from a replaced run leaves the new one alone
+        registry.start("1", "video");
+        events.progress(90.0);
+        assert_eq!(registry.list()[0].progress, 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_wait_finished_covers_stopped_runs() {
+        let registry = std::sync::Arc::new(QuestRegistry::new());
+        let (ticket, _rx) = registry.start("1", "game");
+        registry.stop_all();
+
+        // Stopped but still winding down
+        let waiter = tokio::spawn({
+            let registry = registry.clone();
+            async move { registry.wait_finished().await }
+        });
+        tokio::task::yield_now().await;
+        assert!(!waiter.is_finished());
+
+        registry.finish("1", ticket);
+        waiter.await.unwrap();
+        registry.wait_finished().await;
+    }
+}
@@ -0,0 +1,100 @@
+use crate::error::DiscordApiError;
+use crate::ratelimit::server_delay;
+use serde::Serialize;
+use std::time::Duration;
+
+/// Backoff policy shared by the quest completers
+///
+/// Retryable failures (see [`DiscordApiError::is_retryable`]) are retried with
+/// exponential backoff up to `max_delay`; the quest is only aborted after
+/// `max_consecutive_failures` failures in a row, or on the first fatal error.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    /// Delay before the first retry
+    pub base_delay: Duration,
+    /// Upper bound for any single delay
+    pub max_delay: Duration,
+    /// Failures in a row after which the quest is aborted
+    pub max_consecutive_failures: u32,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            base_delay: Duration::from_secs(2),
+            max_delay: Duration::from_secs(60),
+            max_consecutive_failures: 5,
+        }
+    }
+}
+
+/// Payload of a `quest-retrying` event
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RetryNotice {
+    /// Consecutive failures so far (1 for the first retry)
+    pub attempt: u32,
+    /// Failures after which the quest is aborted
+    pub max_attempts: u32,
+    /// Seconds until the next attempt
+    pub delay_secs: f64,
+    /// Error that caused the retry
+    pub message: String,
+}
+
+impl RetryPolicy {
+    /// Exponential backoff for the `failures`-th consecutive failure, capped at `max_delay`
+    pub fn backoff(&self, failures: u32) -> Duration {
+        let exponent = failures.saturating_sub(1).min(16);
+        self.base_delay
+            .saturating_mul(1 << exponent)
+            .min(self.max_delay)
+    }
+
+    /// How long to wait before retrying after `error`, or `None` to give up
+    ///
+    /// A 429 waits at least as long as Discord asked for.
+    pub fn next_delay(&self, failures: u32, error: &DiscordApiError) -> Option<Duration> {
+        if !error.is_retryable() || failures >= self.max_consecutive_failures {
+            return None;
+        }
+
+        let delay = match error {
+            DiscordApiError::RateLimited { retry_after, .. } => {
+                self.backoff(failures).max(server_delay(*retry_after))
+            }
+            _ => self.backoff(failures),
+        };
+        Some(delay)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transport() -> DiscordApiError {
+        DiscordApiError::Transport { message: "connection reset".into() }
+    }
+
+    #[test]
+    fn test_backoff_is_exponential_and_capped() {
+        let policy = RetryPolicy::default();
+        assert_eq!(policy.backoff(1), Duration::from_secs(2));
+        assert_eq!(policy.backoff(2), Duration::from_secs(4));
+        assert_eq!(policy.backoff(3), Duration::from_secs(8));
+        assert_eq!(policy.backoff(10), Duration::from_secs(60));
+    }
+
+    #[test]
+    fn test_next_delay_classifies_errors() {
+        let policy = RetryPolicy::default();
+        assert_eq!(policy.next_delay(1, &transport()), Some(Duration::from_secs(2)));
+        assert_eq!(policy.next_delay(5, &transport()), None);
+
+        let unauthorized = DiscordApiError::Unauthorized { message: "401".into() };
+        assert_eq!(policy.next_delay(1, &unauthorized), None);
+
+        let limited = DiscordApiError::RateLimited { retry_after: 30.0, global: false, message: "429".into() };
+        assert_eq!(policy.next_delay(1, &limited), Some(Duration::from_secs(30)));
+    }
+}
@@ -1,4 +1,5 @@
 pub use discord_sdk as ds;
+use tracing::{info, warn};
 
 
 /// Application identifier for "Andy's Test App" used in the Discord SDK's
@@ -12,9 +13,9 @@ pub struct Client {
 }
 
 pub async fn make_client(app_id: ds::AppId, subs: ds::Subscriptions) -> Client {
-    println!("Creating Discord client with app ID: {}", app_id);
+    info!("Creating Discord client with app ID: {}", app_id);
     let (wheel, handler) = ds::wheel::Wheel::new(Box::new(|err| {
-        println!("Error: {:?}", err);
+        warn!("Discord SDK error: {:?}", err);
     }));
 
     let mut user = wheel.user();
@@ -28,7 +29,7 @@ pub async fn make_client(app_id: ds::AppId, subs: ds::Subscriptions) -> Client {
         ds::wheel::UserState::Disconnected(err) => panic!("failed to connect to Discord: {}", err),
     };
 
-    println!("connected to Discord, local user is {:#?}", user);
+    info!("connected to Discord, local user is {:?}", user);
 
     Client {
         discord,
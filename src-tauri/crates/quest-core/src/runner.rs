@@ -3,6 +3,7 @@ use discord_sdk::activity::ActivityBuilder;
 
 use crate::rpc::{self, Client};
 use serde::Deserialize;
+use tracing::warn;
 
 #[derive(Deserialize)]
 pub struct ActivityParams {
@@ -24,14 +25,14 @@ pub struct CreateActivityResult {
 
 fn to_app_id(app_id: &str) -> Result<u64, std::num::ParseIntError> {
     app_id.parse::<u64>().map_err(|e| {
-        eprintln!("Failed to parse app_id: {}", e);
-        std::num::ParseIntError::from(e)
+        warn!("Failed to parse app_id: {}", e);
+        e
     })
 }
 
 pub fn parse_activity_json(activity_json: &str) -> Result<ActivityParams, String> {
     serde_json::from_str(activity_json).map_err(|e| {
-        eprintln!("Failed to parse activity JSON: {}", e);
+        warn!("Failed to parse activity JSON: {}", e);
         format!("Failed to parse activity JSON: {}", e)
     })
 }
@@ -40,7 +41,7 @@ pub fn create_activity(activity_json: String) -> Result<CreateActivityResult, St
     let activity: ActivityParams = parse_activity_json(&activity_json)?;
 
     let app_id: u64 = to_app_id(&activity.app_id).map_err(|e| {
-        eprintln!("Failed to parse app_id: {}", e);
+        warn!("Failed to parse app_id: {}", e);
         format!("Failed to parse app_id: {}", e)
     })?;
 
@@ -54,18 +55,16 @@ pub fn create_activity(activity_json: String) -> Result<CreateActivityResult, St
     let mut rp: discord_sdk::activity::ActivityBuilder =
         rpc::ds::activity::ActivityBuilder::default();
 
-    if Some(activity_kind) != None {
-        if activity_kind == 0 {
-            rp = rp.kind(rpc::ds::activity::ActivityKind::Playing);
-        } else if activity_kind == 2 {
-            rp = rp.kind(rpc::ds::activity::ActivityKind::Listening);
-        } else if activity_kind == 3 {
-            rp = rp.kind(rpc::ds::activity::ActivityKind::Watching);
-        } else if activity_kind == 5 {
-            rp = rp.kind(rpc::ds::activity::ActivityKind::Competing);
-        } else {
-            rp = rp.kind(rpc::ds::activity::ActivityKind::Playing);
-        }
+    if activity_kind == 0 {
+        rp = rp.kind(rpc::ds::activity::ActivityKind::Playing);
+    } else if activity_kind == 2 {
+        rp = rp.kind(rpc::ds::activity::ActivityKind::Listening);
+    } else if activity_kind == 3 {
+        rp = rp.kind(rpc::ds::activity::ActivityKind::Watching);
+    } else if activity_kind == 5 {
+        rp = rp.kind(rpc::ds::activity::ActivityKind::Competing);
+    } else {
+        rp = rp.kind(rpc::ds::activity::ActivityKind::Playing);
     }
 
     // details
@@ -80,7 +79,7 @@ pub fn create_activity(activity_json: String) -> Result<CreateActivityResult, St
 
     // timestamp
     if let Some(ts) = timestamp {
-        rp = rp.start_timestamp(ts as i64);
+        rp = rp.start_timestamp(ts);
     }
 
     // large_image_key
@@ -91,7 +90,7 @@ pub fn create_activity(activity_json: String) -> Result<CreateActivityResult, St
 
     Ok(CreateActivityResult {
         activity: rp,
-        app_id: app_id,
+        app_id,
     })
 }
 
@@ -0,0 +1,102 @@
+//! Keeping tokens out of logs, errors and memory dumps
+//!
+//! Tokens are held as [`SecretString`], which never prints its value and wipes
+//! it when dropped. [`redact_tokens`] is the second line of defence: the log
+//! sinks and error messages run text through it, so a token that ended up in
+//! a string anyway (an echoed request, a debug print) is masked by its shape.
+
+use regex::Regex;
+use std::borrow::Cow;
+use std::fmt;
+use std::sync::OnceLock;
+use zeroize::Zeroize;
+
+/// What a secret or a token-shaped string is replaced with
+pub const REDACTED: &str = "[REDACTED]";
+
+/// String that prints as [`REDACTED`] and is zeroed when dropped
+///
+/// Deliberately not `Serialize`, so it can't be sent to the frontend by
+/// accident; call [`SecretString::expose_secret`] where the value is needed.
+#[derive(Clone)]
+pub struct SecretString(String);
+
+impl SecretString {
+    pub fn new(value: String) -> Self {
+        Self(value)
+    }
+
+    /// The actual value, for the request header or payload that needs it
+    pub fn expose_secret(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<String> for SecretString {
+    fn from(value: String) -> Self {
+        Self(value)
+    }
+}
+
+impl From<&str> for SecretString {
+    fn from(value: &str) -> Self {
+        Self(value.to_string())
+    }
+}
+
+impl fmt::Debug for SecretString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SecretString({})", REDACTED)
+    }
+}
+
+impl fmt::Display for SecretString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(REDACTED)
+    }
+}
+
+impl Drop for SecretString {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+/// User tokens (`<base64 user id>.<timestamp>.<hmac>`) and legacy `mfa.` tokens
+fn token_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| {
+        Regex::new(r"mfa\.[A-Za-z0-9_-]{20,}|[A-Za-z0-9_-]{20,}\.[A-Za-z0-9_-]{6,}\.[A-Za-z0-9_-]{20,}").unwrap()
+    })
+}
+
+/// Replace everything shaped like a Discord token with [`REDACTED`]
+pub fn redact_tokens(text: &str) -> Cow<'_, str> {
+    token_pattern().replace_all(text, REDACTED)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOKEN: &str = "MTIzNDU2Nzg5MDEyMzQ1Njc4OTA.GhIjKl.abcdefghijklmnopqrstuvwxyz0123456";
+
+    #[test]
+    fn test_secret_string_never_prints() {
+        let secret = SecretString::from(TOKEN);
+        assert_eq!(format!("{}", secret), REDACTED);
+        assert_eq!(format!("{:?}", secret), "SecretString([REDACTED])");
+        assert_eq!(secret.expose_secret(), TOKEN);
+    }
+
+    #[test]
+    fn test_redact_tokens_by_shape() {
+        let text = format!("Authorization: {} sent to https://discord.com/api/v9/users/@me", TOKEN);
+        assert_eq!(
+            redact_tokens(&text),
+            "Authorization: [REDACTED] sent to https://discord.com/api/v9/users/@me"
+        );
+        assert_eq!(redact_tokens("mfa.abcdefghijklmnopqrstuvwxyz"), REDACTED);
+        assert!(matches!(redact_tokens("quest_core::discord_api"), Cow::Borrowed(_)));
+    }
+}
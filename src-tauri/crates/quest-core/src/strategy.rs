@@ -0,0 +1,285 @@
+use crate::discord_api::DiscordApiClient;
+use crate::error::ApiResult;
+use crate::models::QuestUserStatus;
+use rand::Rng;
+use std::future::Future;
+use std::time::Duration;
+
+/// How to complete one kind of quest task, step by step
+///
+/// [`crate::quest_completer::run_strategy`] owns the loop: it handles stop and
+/// pause, retries failed steps, emits events and sleeps between steps. A
+/// strategy only says what request to send, how often, and what the response
+/// means for progress.
+pub trait QuestStrategy: Send + Sync {
+    /// What a successful request returns
+    type Response: Send;
+
+    /// Short name used in log lines ("Video", "Stream", "Game")
+    fn name(&self) -> &'static str;
+
+    /// Send the request for the current step
+    ///
+    /// Must not change any state: a failed step is sent again as is.
+    fn next_request(&self, client: &DiscordApiClient) -> impl Future<Output = ApiResult<Self::Response>> + Send;
+
+    /// Delay between two accepted requests
+    fn interval(&self) -> Duration;
+
+    /// Real time that passed between steps, with paused time and retry backoff left out
+    ///
+    /// Called after each wait for [`interval`](Self::interval), including one
+    /// cut short by a stop, so [`flush`](Self::flush) can save the position
+    /// actually reached.
+    fn advance(&mut self, _active: Duration) {}
+
+    /// Take in the response to an accepted step and return the progress percentage
+    ///
+    /// Where the response carries Discord's view of the progress, that wins over
+    /// anything counted locally.
+    fn interpret(&mut self, response: Self::Response) -> f64;
+
+    /// Whether the quest needs no more requests
+    fn is_done(&self) -> bool;
+
+    /// Save the exact current position when the run is stopped between steps
+    ///
+    /// The response goes through [`interpret`](Self::interpret) like any step.
+    /// Returns `None` for strategies with nothing to save.
+    fn flush(&self, _client: &DiscordApiClient) -> impl Future<Output = Option<ApiResult<Self::Response>>> + Send {
+        async { None }
+    }
+
+    /// Close the session on Discord's side once the run ends
+    ///
+    /// Called once when the quest completes or is stopped, if Discord accepted
+    /// any request of the run. Returns `None` for strategies that keep no
+    /// session open.
+    fn end_session(&self, _client: &DiscordApiClient) -> impl Future<Output = Option<ApiResult<()>>> + Send {
+        async { None }
+    }
+}
+
+/// Progress on a task as last reported by Discord
+///
+/// Every accepted request returns the quest's `user_status`; that, not a local
+/// counter, decides the emitted progress and when the quest is done.
+struct ServerProgress {
+    /// Task names whose progress counts (Discord keys progress by task)
+    tasks: &'static [&'static str],
+    seconds_needed: u32,
+    seconds_done: f64,
+    completed: bool,
+}
+
+impl ServerProgress {
+    fn new(tasks: &'static [&'static str], seconds_needed: u32, initial_progress: f64) -> Self {
+        Self {
+            tasks,
+            seconds_needed,
+            seconds_done: initial_progress / 100.0 * seconds_needed as f64,
+            completed: false,
+        }
+    }
+
+    /// Take `status` as the new truth and return the progress percentage
+    ///
+    /// A response without progress for our task keeps the last known value.
+    fn update(&mut self, status: &QuestUserStatus) -> f64 {
+        let reported = self
+            .tasks
+            .iter()
+            .filter_map(|task| status.task_progress(task))
+            .reduce(f64::max);
+        if let Some(seconds) = reported {
+            self.seconds_done = seconds;
+        }
+        self.completed = status.is_completed();
+        self.percent()
+    }
+
+    fn percent(&self) -> f64 {
+        if self.completed || self.seconds_needed == 0 {
+            return 100.0;
+        }
+        (self.seconds_done / self.seconds_needed as f64 * 100.0).min(100.0)
+    }
+
+    fn is_done(&self) -> bool {
+        self.completed || self.seconds_done >= self.seconds_needed as f64
+    }
+}
+
+/// Watch a video by posting an advancing timestamp to `/quests/{id}/video-progress`
+///
+/// Based on power0matin's approach: POST { timestamp: seconds }. Each step
+/// starts from the timestamp Discord accepted, so if it clamps a timestamp
+/// that ran ahead, the next one picks up from there.
+pub struct VideoStrategy {
+    quest_id: String,
+    /// Video seconds advanced per real second
+    speed: f64,
+    /// Real seconds between updates
+    interval: u64,
+    progress: ServerProgress,
+    /// Real time played since Discord last accepted a timestamp
+    watched: Duration,
+}
+
+impl VideoStrategy {
+    /// `initial_progress` is a percentage of `seconds_needed`
+    pub fn new(quest_id: String, seconds_needed: u32, initial_progress: f64, speed: f64, interval: u64) -> Self {
+        Self {
+            quest_id,
+            speed,
+            interval,
+            progress: ServerProgress::new(&["WATCH_VIDEO", "WATCH_VIDEO_ON_MOBILE"], seconds_needed, initial_progress),
+            watched: Duration::ZERO,
+        }
+    }
+
+    /// Timestamp of the next step, e.g. 3s further at 1x speed and a 3s interval
+    fn next_timestamp(&self) -> f64 {
+        (self.progress.seconds_done + self.speed * self.interval as f64).min(self.progress.seconds_needed as f64)
+    }
+
+    /// Where playback is right now: the last accepted timestamp plus the video
+    /// time played since, never past the next step's timestamp
+    fn current_position(&self) -> f64 {
+        (self.progress.seconds_done + self.speed * self.watched.as_secs_f64()).min(self.next_timestamp())
+    }
+}
+
+impl QuestStrategy for VideoStrategy {
+    type Response = QuestUserStatus;
+
+    fn name(&self) -> &'static str {
+        "Video"
+    }
+
+    async fn next_request(&self, client: &DiscordApiClient) -> ApiResult<QuestUserStatus> {
+        // Add some randomness to look more natural
+        let timestamp = self.next_timestamp() + rand::rng().random_range(0.0..0.5);
+        client.update_video_progress(&self.quest_id, timestamp).await
+    }
+
+    fn interval(&self) -> Duration {
+        Duration::from_secs(self.interval)
+    }
+
+    fn advance(&mut self, active: Duration) {
+        self.watched += active;
+    }
+
+    fn interpret(&mut self, status: QuestUserStatus) -> f64 {
+        self.watched = Duration::ZERO;
+        self.progress.update(&status)
+    }
+
+    fn is_done(&self) -> bool {
+        self.progress.is_done()
+    }
+
+    async fn flush(&self, client: &DiscordApiClient) -> Option<ApiResult<QuestUserStatus>> {
+        // Nothing was watched since the last accepted position
+        if self.watched.is_zero() {
+            return None;
+        }
+        Some(client.update_video_progress(&self.quest_id, self.current_position()).await)
+    }
+}
+
+/// Keep a stream alive with a heartbeat every 30 seconds
+pub struct StreamStrategy {
+    quest_id: String,
+    stream_key: String,
+    progress: ServerProgress,
+}
+
+impl StreamStrategy {
+    const INTERVAL: u64 = 30;
+
+    pub fn new(quest_id: String, stream_key: String, seconds_needed: u32, initial_progress: f64) -> Self {
+        Self {
+            quest_id,
+            stream_key,
+            progress: ServerProgress::new(&["STREAM_ON_DESKTOP"], seconds_needed, initial_progress),
+        }
+    }
+}
+
+impl QuestStrategy for StreamStrategy {
+    type Response = QuestUserStatus;
+
+    fn name(&self) -> &'static str {
+        "Stream"
+    }
+
+    async fn next_request(&self, client: &DiscordApiClient) -> ApiResult<QuestUserStatus> {
+        client.send_stream_heartbeat(&self.quest_id, &self.stream_key).await
+    }
+
+    fn interval(&self) -> Duration {
+        Duration::from_secs(Self::INTERVAL)
+    }
+
+    fn interpret(&mut self, status: QuestUserStatus) -> f64 {
+        self.progress.update(&status)
+    }
+
+    fn is_done(&self) -> bool {
+        self.progress.is_done()
+    }
+}
+
+/// Play a game without running it, by sending the client's heartbeat directly
+///
+/// Based on HAR analysis: POST { application_id, terminal: false } every 60 seconds,
+/// and one `terminal: true` when the game "closes"
+pub struct GameHeartbeatStrategy {
+    quest_id: String,
+    application_id: String,
+    progress: ServerProgress,
+}
+
+impl GameHeartbeatStrategy {
+    /// Fixed heartbeat interval, based on Discord client behavior
+    pub const INTERVAL: u64 = 60;
+
+    pub fn new(quest_id: String, application_id: String, seconds_needed: u32, initial_progress: f64) -> Self {
+        Self {
+            quest_id,
+            application_id,
+            progress: ServerProgress::new(&["PLAY_ON_DESKTOP"], seconds_needed, initial_progress),
+        }
+    }
+}
+
+impl QuestStrategy for GameHeartbeatStrategy {
+    type Response = QuestUserStatus;
+
+    fn name(&self) -> &'static str {
+        "Game"
+    }
+
+    async fn next_request(&self, client: &DiscordApiClient) -> ApiResult<QuestUserStatus> {
+        client.send_game_heartbeat(&self.quest_id, &self.application_id, false).await
+    }
+
+    fn interval(&self) -> Duration {
+        Duration::from_secs(Self::INTERVAL)
+    }
+
+    fn interpret(&mut self, status: QuestUserStatus) -> f64 {
+        self.progress.update(&status)
+    }
+
+    fn is_done(&self) -> bool {
+        self.progress.is_done()
+    }
+
+    async fn end_session(&self, client: &DiscordApiClient) -> Option<ApiResult<()>> {
+        let result = client.send_game_heartbeat(&self.quest_id, &self.application_id, true).await;
+        Some(result.map(|_| ()))
+    }
+}
@@ -0,0 +1,24 @@
+//! Fixtures shared by tests that run against [`mock_discord::MockDiscord`]
+//!
+//! Kept here rather than in `mock-discord`, which can't depend on this crate:
+//! it is this crate's dev-dependency, and unit tests would see a second copy
+//! of every type.
+
+use crate::discord_api::DiscordApiClient;
+use crate::endpoints::DiscordEndpoints;
+use mock_discord::{MockDiscord, MOCK_TOKEN};
+
+/// REST and gateway endpoints of `mock`
+pub fn mock_endpoints(mock: &MockDiscord) -> DiscordEndpoints {
+    DiscordEndpoints::local(&mock.rest_base(), &mock.gateway_url())
+}
+
+/// Client for `mock`, logged in with [`MOCK_TOKEN`]
+pub fn mock_client(mock: &MockDiscord) -> DiscordApiClient {
+    mock_client_with_token(mock, MOCK_TOKEN)
+}
+
+/// Client for `mock` that sends `token` instead
+pub fn mock_client_with_token(mock: &MockDiscord, token: &str) -> DiscordApiClient {
+    DiscordApiClient::new(token.to_string(), mock_endpoints(mock)).unwrap()
+}
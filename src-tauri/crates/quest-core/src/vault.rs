@@ -0,0 +1,522 @@
+//! Passphrase-protected vault for tokens entered by hand
+//!
+//! Accounts saved here are written to `credentials.vault` in the app data dir,
+//! encrypted with AES-256-GCM under a key derived from the user's passphrase
+//! (PBKDF2-HMAC-SHA256). Neither the passphrase nor the key is ever written
+//! out: while the vault is unlocked the key and the decrypted tokens live in
+//! memory only, and [`CredentialVault::run_auto_lock`] drops them again once
+//! the vault has been idle for its auto-lock time.
+
+use crate::accounts::new_handle;
+use crate::models::{DiscordUser, ExtractedAccount};
+use crate::secret::SecretString;
+use aes_gcm::aead::{Aead, KeyInit};
+use aes_gcm::{Aes256Gcm, Nonce};
+use anyhow::{anyhow, bail, Context, Result};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use pbkdf2::pbkdf2_hmac;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::time::Instant;
+use tracing::{info, warn};
+use zeroize::{Zeroize, Zeroizing};
+
+/// File the vault is kept in, inside the app data dir
+pub const VAULT_FILE_NAME: &str = "credentials.vault";
+
+/// PBKDF2 rounds for newly created vaults; existing files keep their own count
+pub const KDF_ITERATIONS: u32 = 600_000;
+
+/// Range of PBKDF2 rounds accepted from a vault file; anything else is treated as tampering
+pub const KDF_ITERATIONS_MIN: u32 = 10_000;
+pub const KDF_ITERATIONS_MAX: u32 = 10_000_000;
+
+/// Idle time after which an unlocked vault locks itself, unless configured otherwise
+pub const DEFAULT_AUTO_LOCK_SECS: u64 = 15 * 60;
+
+const VAULT_VERSION: u32 = 1;
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+const AUTO_LOCK_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// On-disk layout; everything except the ciphertext is needed before unlocking
+#[derive(Serialize, Deserialize)]
+struct VaultFile {
+    version: u32,
+    iterations: u32,
+    /// Base64 of the PBKDF2 salt
+    salt: String,
+    /// Base64 of the AES-GCM nonce, fresh on every write
+    nonce: String,
+    /// Base64 of the encrypted JSON list of [`StoredAccount`]s
+    ciphertext: String,
+    auto_lock_secs: u64,
+}
+
+/// Plaintext entry of the vault
+#[derive(Serialize, Deserialize)]
+struct StoredAccount {
+    user: DiscordUser,
+    token: String,
+}
+
+impl Drop for StoredAccount {
+    fn drop(&mut self) {
+        self.token.zeroize();
+    }
+}
+
+/// What the UI needs to know to offer saving or unlocking
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct VaultStatus {
+    /// A vault file exists in the app data dir
+    pub exists: bool,
+    pub unlocked: bool,
+    pub auto_lock_secs: u64,
+}
+
+struct VaultAccount {
+    handle: String,
+    user: DiscordUser,
+    token: SecretString,
+}
+
+/// Key and contents of an unlocked vault
+struct Unlocked {
+    key: Zeroizing<[u8; 32]>,
+    salt: [u8; SALT_LEN],
+    iterations: u32,
+    accounts: Vec<VaultAccount>,
+}
+
+impl Unlocked {
+    fn extracted_accounts(&self) -> Vec<ExtractedAccount> {
+        self.accounts
+            .iter()
+            .map(|a| ExtractedAccount { handle: a.handle.clone(), user: a.user.clone() })
+            .collect()
+    }
+}
+
+struct VaultState {
+    unlocked: Option<Unlocked>,
+    auto_lock: Duration,
+    last_used: Instant,
+}
+
+/// Encrypted store of manually entered tokens
+///
+/// Accounts are handed to the UI as [`ExtractedAccount`]s with opaque handles,
+/// the same way detected accounts are; [`CredentialVault::token`] resolves a
+/// handle while the vault is unlocked.
+pub struct CredentialVault {
+    path: PathBuf,
+    /// PBKDF2 rounds used when a new vault is created
+    iterations: u32,
+    state: Mutex<VaultState>,
+    /// Held while writing or deleting the file, so writes land in the order they were made
+    write: Mutex<()>,
+}
+
+fn derive_key(passphrase: &SecretString, salt: &[u8], iterations: u32) -> Zeroizing<[u8; 32]> {
+    let mut key = Zeroizing::new([0u8; 32]);
+    pbkdf2_hmac::<Sha256>(passphrase.expose_secret().as_bytes(), salt, iterations, key.as_mut());
+    key
+}
+
+impl CredentialVault {
+    /// Vault at `<dir>/credentials.vault`; the file is only created by [`save`](Self::save)
+    pub fn open(dir: &Path) -> Self {
+        Self::with_iterations(dir.join(VAULT_FILE_NAME), KDF_ITERATIONS)
+    }
+
+    fn with_iterations(path: PathBuf, iterations: u32) -> Self {
+        let auto_lock_secs = match Self::read(&path) {
+            Ok(Some(file)) => file.auto_lock_secs,
+            Ok(None) => DEFAULT_AUTO_LOCK_SECS,
+            Err(e) => {
+                warn!("Ignoring credential vault {}: {:#}", path.display(), e);
+                DEFAULT_AUTO_LOCK_SECS
+            }
+        };
+
+        Self {
+            path,
+            iterations,
+            state: Mutex::new(VaultState {
+                unlocked: None,
+                auto_lock: Duration::from_secs(auto_lock_secs),
+                last_used: Instant::now(),
+            }),
+            write: Mutex::new(()),
+        }
+    }
+
+    fn read(path: &Path) -> Result<Option<VaultFile>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let contents = fs::read_to_string(path).context("Failed to read credential vault")?;
+        let file: VaultFile = serde_json::from_str(&contents).context("Failed to parse credential vault")?;
+        if file.version != VAULT_VERSION {
+            bail!("Unsupported credential vault version {}", file.version);
+        }
+        Ok(Some(file))
+    }
+
+    /// Write the vault file (via a temp file, so a crash mid-write keeps the old copy)
+    fn write(&self, file: &VaultFile) -> Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let tmp = self.path.with_extension("vault.tmp");
+        fs::write(&tmp, serde_json::to_vec_pretty(file)?)?;
+        fs::rename(&tmp, &self.path)?;
+        Ok(())
+    }
+
+    /// Encrypt `unlocked` under a fresh nonce
+    fn seal(unlocked: &Unlocked, auto_lock: Duration) -> Result<VaultFile> {
+        let stored: Vec<StoredAccount> = unlocked
+            .accounts
+            .iter()
+            .map(|a| StoredAccount { user: a.user.clone(), token: a.token.expose_secret().to_string() })
+            .collect();
+        let plaintext = Zeroizing::new(serde_json::to_vec(&stored)?);
+
+        let mut nonce = [0u8; NONCE_LEN];
+        rand::rng().fill(&mut nonce);
+        let cipher = Aes256Gcm::new_from_slice(unlocked.key.as_ref()).map_err(|e| anyhow!("Invalid vault key: {}", e))?;
+        let ciphertext = cipher
+            .encrypt(Nonce::from_slice(&nonce), plaintext.as_slice())
+            .map_err(|_| anyhow!("Failed to encrypt credential vault"))?;
+
+        Ok(VaultFile {
+            version: VAULT_VERSION,
+            iterations: unlocked.iterations,
+            salt: BASE64.encode(unlocked.salt),
+            nonce: BASE64.encode(nonce),
+            ciphertext: BASE64.encode(ciphertext),
+            auto_lock_secs: auto_lock.as_secs(),
+        })
+    }
+
+    /// Derive the key for the existing file and decrypt it
+    fn decrypt(file: &VaultFile, passphrase: &SecretString) -> Result<Unlocked> {
+        // The count comes from the file: too low would weaken the key, too high would hang unlocking
+        if !(KDF_ITERATIONS_MIN..=KDF_ITERATIONS_MAX).contains(&file.iterations) {
+            bail!("Damaged credential vault: unsupported iteration count {}", file.iterations);
+        }
+        let salt: [u8; SALT_LEN] = BASE64
+            .decode(&file.salt)
+            .ok()
+            .and_then(|s| s.try_into().ok())
+            .context("Damaged credential vault")?;
+        let nonce = BASE64.decode(&file.nonce).context("Damaged credential vault")?;
+        let ciphertext = BASE64.decode(&file.ciphertext).context("Damaged credential vault")?;
+        if nonce.len() != NONCE_LEN {
+            bail!("Damaged credential vault");
+        }
+
+        let key = derive_key(passphrase, &salt, file.iterations);
+        let cipher = Aes256Gcm::new_from_slice(key.as_ref()).map_err(|e| anyhow!("Invalid vault key: {}", e))?;
+        let plaintext = Zeroizing::new(
+            cipher
+                .decrypt(Nonce::from_slice(&nonce), ciphertext.as_slice())
+                .map_err(|_| anyhow!("Wrong passphrase or damaged vault"))?,
+        );
+        let mut stored: Vec<StoredAccount> =
+            serde_json::from_slice(&plaintext).context("Damaged credential vault")?;
+
+        let accounts = stored
+            .iter_mut()
+            .map(|a| VaultAccount {
+                handle: new_handle(),
+                user: a.user.clone(),
+                token: SecretString::new(std::mem::take(&mut a.token)),
+            })
+            .collect();
+
+        Ok(Unlocked { key, salt, iterations: file.iterations, accounts })
+    }
+
+    pub fn status(&self) -> VaultStatus {
+        let state = self.state.lock().unwrap();
+        VaultStatus {
+            exists: self.path.exists(),
+            unlocked: state.unlocked.is_some(),
+            auto_lock_secs: state.auto_lock.as_secs(),
+        }
+    }
+
+    /// Unlock with `passphrase` and return the saved accounts
+    pub fn unlock(&self, passphrase: &SecretString) -> Result<Vec<ExtractedAccount>> {
+        let file = Self::read(&self.path)?.context("No credential vault has been saved")?;
+        let unlocked = Self::decrypt(&file, passphrase)?;
+        let accounts = unlocked.extracted_accounts();
+
+        let mut state = self.state.lock().unwrap();
+        state.unlocked = Some(unlocked);
+        state.auto_lock = Duration::from_secs(file.auto_lock_secs);
+        state.last_used = Instant::now();
+        info!("Credential vault unlocked ({} accounts)", accounts.len());
+        Ok(accounts)
+    }
+
+    /// Add `token` for `user`, replacing an earlier entry for the same user
+    ///
+    /// An unlocked vault is re-encrypted with its current key and takes no
+    /// passphrase. A locked or not yet created vault needs `passphrase`, and
+    /// is left unlocked.
+    pub fn save(
+        &self,
+        passphrase: Option<&SecretString>,
+        user: DiscordUser,
+        token: SecretString,
+    ) -> Result<Vec<ExtractedAccount>> {
+        // Key derivation is slow on purpose, so it runs without the state lock held
+        let is_unlocked = self.state.lock().unwrap().unlocked.is_some();
+        let opened = match (is_unlocked, passphrase) {
+            (true, Some(_)) => bail!("The vault is already unlocked, save without a passphrase"),
+            (true, None) => None,
+            (false, None) => bail!("A passphrase is needed to save to the vault"),
+            (false, Some(passphrase)) => Some(match Self::read(&self.path)? {
+                Some(file) => Self::decrypt(&file, passphrase)?,
+                None => {
+                    let mut salt = [0u8; SALT_LEN];
+                    rand::rng().fill(&mut salt);
+                    Unlocked {
+                        key: derive_key(passphrase, &salt, self.iterations),
+                        salt,
+                        iterations: self.iterations,
+                        accounts: Vec::new(),
+                    }
+                }
+            }),
+        };
+
+        let _write = self.write.lock().unwrap();
+        let (file, accounts) = {
+            let mut state = self.state.lock().unwrap();
+            if let Some(opened) = opened {
+                state.unlocked.get_or_insert(opened);
+            }
+            let auto_lock = state.auto_lock;
+            let unlocked = state.unlocked.as_mut().context("The vault was locked while saving, try again")?;
+            match unlocked.accounts.iter_mut().find(|a| a.user.id == user.id) {
+                Some(account) => {
+                    account.user = user;
+                    account.token = token;
+                }
+                None => unlocked.accounts.push(VaultAccount { handle: new_handle(), user, token }),
+            }
+            let file = Self::seal(unlocked, auto_lock)?;
+            let accounts = unlocked.extracted_accounts();
+            state.last_used = Instant::now();
+            (file, accounts)
+        };
+        self.write(&file)
+            .with_context(|| format!("Failed to save credential vault {}", self.path.display()))?;
+
+        info!("Saved account to credential vault ({} accounts)", accounts.len());
+        Ok(accounts)
+    }
+
+    /// Drop the key and decrypted tokens; returns whether the vault was unlocked
+    pub fn lock(&self) -> bool {
+        let was_unlocked = self.state.lock().unwrap().unlocked.take().is_some();
+        if was_unlocked {
+            info!("Credential vault locked");
+        }
+        was_unlocked
+    }
+
+    /// Lock the vault and delete its file
+    pub fn forget(&self) -> Result<()> {
+        self.lock();
+        let _write = self.write.lock().unwrap();
+        if self.path.exists() {
+            fs::remove_file(&self.path)
+                .with_context(|| format!("Failed to delete credential vault {}", self.path.display()))?;
+            info!("Credential vault deleted");
+        }
+        Ok(())
+    }
+
+    /// Token behind `handle` while the vault is unlocked; counts as use for auto-lock
+    pub fn token(&self, handle: &str) -> Option<SecretString> {
+        let mut state = self.state.lock().unwrap();
+        let token = state
+            .unlocked
+            .as_ref()?
+            .accounts
+            .iter()
+            .find(|a| a.handle == handle)
+            .map(|a| a.token.clone())?;
+        state.last_used = Instant::now();
+        Some(token)
+    }
+
+    /// Change the idle time before the vault locks itself, kept in the vault file
+    pub fn set_auto_lock(&self, secs: u64) -> Result<()> {
+        if secs == 0 {
+            bail!("Auto-lock time must be at least one second");
+        }
+        let _write = self.write.lock().unwrap();
+        {
+            let mut state = self.state.lock().unwrap();
+            state.auto_lock = Duration::from_secs(secs);
+            state.last_used = Instant::now();
+        }
+
+        // The header isn't encrypted, so this works while locked too
+        if let Some(mut file) = Self::read(&self.path)? {
+            file.auto_lock_secs = secs;
+            self.write(&file)
+                .with_context(|| format!("Failed to save credential vault {}", self.path.display()))?;
+        }
+        Ok(())
+    }
+
+    /// Lock if the vault hasn't been used for its auto-lock time; returns whether it locked
+    pub fn lock_if_idle(&self) -> bool {
+        let mut state = self.state.lock().unwrap();
+        if state.unlocked.is_some() && state.last_used.elapsed() >= state.auto_lock {
+            state.unlocked = None;
+            info!("Credential vault locked after {}s idle", state.auto_lock.as_secs());
+            return true;
+        }
+        false
+    }
+
+    /// Check for idleness every second, for as long as the app runs
+    pub async fn run_auto_lock(self: Arc<Self>) {
+        let mut interval = tokio::time::interval(AUTO_LOCK_CHECK_INTERVAL);
+        loop {
+            interval.tick().await;
+            self.lock_if_idle();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::time::sleep;
+
+    const TOKEN: &str = "MTIzNDU2Nzg5MDEyMzQ1Njc4OTA.GhIjKl.abcdefghijklmnopqrstuvwxyz0123456";
+
+    fn user(id: &str) -> DiscordUser {
+        DiscordUser {
+            id: id.to_string(),
+            username: format!("user{}", id),
+            discriminator: "0".to_string(),
+            avatar: None,
+            global_name: None,
+        }
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("dqh-vault-test-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    /// Cheap key derivation, the real round count makes tests slow
+    fn vault(dir: &Path) -> CredentialVault {
+        CredentialVault::with_iterations(dir.join(VAULT_FILE_NAME), KDF_ITERATIONS_MIN)
+    }
+
+    #[test]
+    fn test_saved_token_survives_restart_only_with_passphrase() {
+        let dir = temp_dir("roundtrip");
+        let passphrase = SecretString::from("correct horse battery staple");
+
+        let first = vault(&dir);
+        assert!(first.save(None, user("1"), TOKEN.into()).is_err());
+        let saved = first.save(Some(&passphrase), user("1"), TOKEN.into()).unwrap();
+        assert_eq!(saved.len(), 1);
+        assert!(first.status().unlocked);
+        // Once unlocked the current key is used; a passphrase that might not match it is refused
+        let err = first.save(Some(&"another passphrase".into()), user("2"), TOKEN.into()).unwrap_err();
+        assert_eq!(err.to_string(), "The vault is already unlocked, save without a passphrase");
+
+        let contents = fs::read_to_string(dir.join(VAULT_FILE_NAME)).unwrap();
+        assert!(!contents.contains(TOKEN));
+        assert!(!contents.contains("user1"));
+
+        let second = vault(&dir);
+        assert_eq!(
+            second.status(),
+            VaultStatus { exists: true, unlocked: false, auto_lock_secs: DEFAULT_AUTO_LOCK_SECS }
+        );
+        let err = second.unlock(&"wrong passphrase".into()).unwrap_err();
+        assert_eq!(err.to_string(), "Wrong passphrase or damaged vault");
+        assert!(!second.status().unlocked);
+
+        let accounts = second.unlock(&passphrase).unwrap();
+        assert_eq!(accounts.len(), 1);
+        assert_eq!(accounts[0].user.id, "1");
+        assert_eq!(second.token(&accounts[0].handle).unwrap().expose_secret(), TOKEN);
+
+        // Saving the same user again replaces the entry
+        let accounts = second.save(None, user("1"), "other-token".into()).unwrap();
+        assert_eq!(accounts.len(), 1);
+        assert_eq!(second.token(&accounts[0].handle).unwrap().expose_secret(), "other-token");
+
+        assert!(second.lock());
+        assert!(second.token(&accounts[0].handle).is_none());
+
+        second.forget().unwrap();
+        assert!(!dir.join(VAULT_FILE_NAME).exists());
+        assert!(second.unlock(&passphrase).is_err());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_rejects_tampered_iteration_count() {
+        let dir = temp_dir("iterations");
+        let passphrase = SecretString::from("passphrase");
+        vault(&dir).save(Some(&passphrase), user("1"), TOKEN.into()).unwrap();
+
+        let path = dir.join(VAULT_FILE_NAME);
+        let original: serde_json::Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        for iterations in [0, 1, KDF_ITERATIONS_MAX + 1, u32::MAX] {
+            let mut tampered = original.clone();
+            tampered["iterations"] = iterations.into();
+            fs::write(&path, tampered.to_string()).unwrap();
+
+            let err = vault(&dir).unlock(&passphrase).unwrap_err();
+            assert!(err.to_string().contains("unsupported iteration count"), "{}", err);
+        }
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_locks_after_idle_time() {
+        let dir = temp_dir("autolock");
+        let vault = Arc::new(vault(&dir));
+        vault.set_auto_lock(60).unwrap();
+        let handle = vault.save(Some(&"passphrase".into()), user("1"), TOKEN.into()).unwrap()[0].handle.clone();
+        tokio::spawn(vault.clone().run_auto_lock());
+
+        // Using a token resets the idle timer
+        sleep(Duration::from_secs(45)).await;
+        assert!(vault.token(&handle).is_some());
+        sleep(Duration::from_secs(45)).await;
+        assert!(vault.status().unlocked);
+
+        sleep(Duration::from_secs(20)).await;
+        assert!(!vault.status().unlocked);
+        assert!(vault.token(&handle).is_none());
+
+        // The auto-lock time was kept in the file
+        assert_eq!(CredentialVault::open(&dir).status().auto_lock_secs, 60);
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
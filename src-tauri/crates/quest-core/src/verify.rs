@@ -0,0 +1,90 @@
+use crate::discord_api::DiscordApiClient;
+use crate::quest_completer::{self, QuestControl};
+use crate::quest_events::QuestEvents;
+use crate::retry::RetryPolicy;
+use serde::Serialize;
+use tokio::sync::mpsc::Receiver;
+use tracing::{info, warn};
+
+/// Refetches before a completion is reported as unverified
+pub const VERIFY_ATTEMPTS: u32 = 4;
+
+/// What Discord had recorded for a quest after a completer reported it done
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct VerificationReport {
+    pub quest_id: String,
+    /// `completed_at` was set
+    pub verified: bool,
+    pub completed_at: Option<String>,
+    /// Progress percentage on the quest's most advanced task, as last observed
+    pub progress: Option<f64>,
+    /// Refetches made, including the successful one
+    pub attempts: u32,
+    /// Why the last refetch didn't give an answer (request failed, quest missing)
+    pub error: Option<String>,
+}
+
+/// Refetch `/quests/@me` until `quest_id` shows a `completed_at`
+///
+/// Discord can take a moment to record a completion, so a quest that isn't
+/// marked yet is retried like a failed request, with the default backoff.
+/// The waits between refetches honour pause and stop like the completers'
+/// do; a stop ends verification without a report.
+pub async fn verify_completion<E: QuestEvents + ?Sized>(
+    client: &DiscordApiClient,
+    quest_id: &str,
+    events: &E,
+    control_rx: &mut Receiver<QuestControl>,
+) -> Option<VerificationReport> {
+    let policy = RetryPolicy::default();
+    let mut report = VerificationReport {
+        quest_id: quest_id.to_string(),
+        verified: false,
+        completed_at: None,
+        progress: None,
+        attempts: 0,
+        error: None,
+    };
+
+    for attempt in 1..=VERIFY_ATTEMPTS {
+        if attempt > 1 && !quest_completer::wait(policy.backoff(attempt - 1), control_rx, events).await {
+            info!("Quest {} verification stopped after {} attempt(s)", quest_id, report.attempts);
+            return None;
+        }
+        report.attempts = attempt;
+
+        let quests = match client.get_quests().await {
+            Ok(quests) => quests,
+            Err(e) => {
+                report.error = Some(e.to_string());
+                continue;
+            }
+        };
+        let Some(quest) = quests.into_iter().find(|q| q.id == quest_id) else {
+            report.error = Some(format!("Quest {} not found", quest_id));
+            continue;
+        };
+
+        report.error = None;
+        report.progress = quest
+            .tasks()
+            .keys()
+            .map(|task| quest.task_percent(task))
+            .reduce(f64::max);
+        report.completed_at = quest.user_status.and_then(|s| s.completed_at);
+        if report.completed_at.is_some() {
+            report.verified = true;
+            break;
+        }
+    }
+
+    if report.verified {
+        info!("Quest {} completion verified after {} attempt(s)", quest_id, report.attempts);
+    } else {
+        warn!(
+            "Quest {} completion not recorded by Discord (progress {:?}, error {:?})",
+            quest_id, report.progress, report.error
+        );
+    }
+    Some(report)
+}
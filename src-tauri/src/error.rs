@@ -0,0 +1,62 @@
+use quest_core::error::DiscordApiError;
+use quest_core::secret::redact_tokens;
+use serde::ser::SerializeStruct;
+use serde::{Serialize, Serializer};
+
+/// Error returned by Tauri commands
+///
+/// Always serialized as an object with a stable `kind` and a human readable
+/// `message`; API failures keep the extra fields of [`DiscordApiError`]
+/// (e.g. `retry_after` for `rate_limited`). Token-shaped strings in messages
+/// are masked.
+#[derive(Debug)]
+pub enum CommandError {
+    /// No account selected yet
+    NotLoggedIn,
+    /// Failure reported by the Discord API client
+    Api(DiscordApiError),
+    /// Anything else (token extraction, simulator, ...)
+    Other(String),
+}
+
+impl std::fmt::Display for CommandError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CommandError::NotLoggedIn => write!(f, "Not logged in"),
+            CommandError::Api(e) => write!(f, "{}", e),
+            // Messages from token extraction and the like may quote what they were working on
+            CommandError::Other(message) => write!(f, "{}", redact_tokens(message)),
+        }
+    }
+}
+
+impl From<DiscordApiError> for CommandError {
+    fn from(e: DiscordApiError) -> Self {
+        CommandError::Api(e)
+    }
+}
+
+/// Keeps the structured form of API failures that were wrapped in an `anyhow::Error`
+impl From<anyhow::Error> for CommandError {
+    fn from(e: anyhow::Error) -> Self {
+        match e.downcast::<DiscordApiError>() {
+            Ok(api) => CommandError::Api(api),
+            Err(e) => CommandError::Other(e.to_string()),
+        }
+    }
+}
+
+impl Serialize for CommandError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let kind = match self {
+            CommandError::Api(e) => return e.serialize(serializer),
+            CommandError::NotLoggedIn => "not_logged_in",
+            CommandError::Other(_) => "other",
+        };
+
+        let mut state = serializer.serialize_struct("CommandError", 2)?;
+        state.serialize_field("kind", kind)?;
+        state.serialize_field("message", &self.to_string())?;
+        state.end()
+    }
+}
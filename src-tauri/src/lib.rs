@@ -1,65 +1,106 @@
 // Prevents additional console window on Windows in release
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
-mod discord_api;
-mod discord_gateway;
-mod game_simulator;
-mod models;
-mod quest_completer;
+mod error;
+mod tauri_events;
 mod token_extractor;
 
-use discord_api::DiscordApiClient;
-use models::*;
-use std::sync::Mutex;
-use tauri::{Emitter, Listener, State};
+use error::CommandError;
+use quest_core::accounts::AccountStore;
+use quest_core::discord_api::DiscordApiClient;
+use quest_core::dispatch::{self, Prepared, RunOptions};
+use quest_core::endpoints::DiscordEndpoints;
+use quest_core::jobs::{JobStore, JobTask, PersistingEvents, QuestJob};
+use quest_core::logging::{self, LogEntry, RecentLogs, RotatingFile};
+use quest_core::models::*;
+use quest_core::queue::{self, QueueEvents, QueueSnapshot, QuestQueue};
+use quest_core::registry::{QuestRegistry, RegisteredEvents, RunningQuestInfo};
+use quest_core::secret::SecretString;
+use quest_core::vault::{CredentialVault, VaultStatus};
+use quest_core::{game_simulator, rpc, runner};
+use tauri_events::{TauriQueueEvents, TauriQuestEvents};
+use std::sync::{Arc, Mutex};
+use tauri::{Emitter, Listener, Manager, State};
+use tracing::{debug, error, info, warn};
+
+/// How long exit waits for stopped quests to flush progress and send terminal heartbeats
+const SHUTDOWN_GRACE: std::time::Duration = std::time::Duration::from_secs(5);
 
 /// Global state: Discord API client
 struct AppState {
     client: Mutex<Option<DiscordApiClient>>,
-    quest_state: Mutex<Option<QuestState>>,
+    /// Account the client is logged in as
+    user: Mutex<Option<DiscordUser>>,
+    /// Tokens of detected accounts; the frontend only gets their handles
+    accounts: AccountStore,
+    /// Passphrase-protected store for manually entered tokens, `None` without an app data dir
+    vault: Option<Arc<CredentialVault>>,
+    /// Quests running in the background, keyed by quest id
+    quests: Arc<QuestRegistry>,
+    /// Quests waiting to be run one after another
+    queue: Arc<QuestQueue>,
+    /// Started quests persisted under the app data dir, for resuming after a restart
+    jobs: Arc<JobStore>,
+    /// Discord endpoints every API client is created with
+    endpoints: DiscordEndpoints,
+    /// Latest log entries, for `get_recent_logs`
+    logs: RecentLogs,
+}
+
+/// Clone of the logged-in API client
+fn logged_in_client(state: &AppState) -> Result<DiscordApiClient, CommandError> {
+    state
+        .client
+        .lock()
+        .unwrap()
+        .as_ref()
+        .cloned()
+        .ok_or(CommandError::NotLoggedIn)
 }
 
 /// Auto-detect Discord tokens (returns all valid accounts found)
+///
+/// Accounts are returned by handle; log in with `select_account`.
 #[tauri::command]
-async fn auto_detect_token(_state: State<'_, AppState>) -> Result<Vec<ExtractedAccount>, String> {
+async fn auto_detect_token(state: State<'_, AppState>) -> Result<Vec<ExtractedAccount>, CommandError> {
     // Extract tokens
     let tokens = token_extractor::extract_tokens()
-        .map_err(|e| format!("Token extraction failed: {}", e))?;
+        .map_err(|e| CommandError::Other(format!("Token extraction failed: {}", e)))?;
 
     let mut valid_accounts = Vec::new();
-    let mut last_error = String::new();
+    let mut last_error = None;
     
-    println!("Validating {} tokens...", tokens.len());
+    info!("Validating {} tokens...", tokens.len());
 
     for (index, token) in tokens.iter().enumerate() {
-        println!("Validating token {}/{}", index + 1, tokens.len());
+        debug!("Validating token {}/{}", index + 1, tokens.len());
         // Create API client
-        if let Ok(client) = DiscordApiClient::new(token.clone()) {
+        if let Ok(client) = DiscordApiClient::new(token.clone(), state.endpoints.clone()) {
             // Validate token
             match client.get_current_user().await {
                 Ok(user) => {
-                    println!("Token {} valid", index);
+                    debug!("Token {} valid", index);
                     valid_accounts.push(ExtractedAccount {
-                        token: token.clone(),
+                        handle: state.accounts.add(token.clone()),
                         user,
                     });
                 }
                 Err(e) => {
-                    println!("Token {} invalid: {}", index, e);
-                    last_error = format!("Token validation failed: {}", e);
+                    warn!("Token {} invalid: {}", index, e);
+                    last_error = Some(e);
                     // Continue to next token
                 }
             }
         }
     }
     
-    println!("Found {} valid accounts", valid_accounts.len());
+    info!("Found {} valid accounts", valid_accounts.len());
 
     if valid_accounts.is_empty() {
-        return Err(if !last_error.is_empty() { 
-            format!("No valid accounts found. Last error: {}", last_error) 
-        } else { 
-            "No valid accounts found".to_string() 
+        // Surface the last validation failure so the UI can tell e.g. a rate limit from a bad token
+        return Err(match last_error {
+            Some(e) => CommandError::Api(e),
+            None => CommandError::Other("No valid accounts found".to_string()),
         });
     }
 
@@ -70,94 +111,197 @@ async fn auto_detect_token(_state: State<'_, AppState>) -> Result<Vec<ExtractedA
 
 /// Login with provided token
 #[tauri::command]
-async fn set_token(token: String, state: State<'_, AppState>) -> Result<DiscordUser, String> {
+async fn set_token(token: String, state: State<'_, AppState>) -> Result<DiscordUser, CommandError> {
+    login(token.into(), &state).await
+}
+
+/// Login as an account returned by `auto_detect_token` or `unlock_vault`
+#[tauri::command]
+async fn select_account(handle: String, state: State<'_, AppState>) -> Result<DiscordUser, CommandError> {
+    let token = state
+        .accounts
+        .token(&handle)
+        .or_else(|| state.vault.as_ref().and_then(|vault| vault.token(&handle)))
+        .ok_or_else(|| CommandError::Other("Unknown account, detect accounts or unlock the vault again".to_string()))?;
+    login(token, &state).await
+}
+
+/// Validate `token` and make it the logged-in client
+async fn login(token: SecretString, state: &AppState) -> Result<DiscordUser, CommandError> {
     // Create API client
-    let client = DiscordApiClient::new(token)
-        .map_err(|e| format!("Failed to create API client: {}", e))?;
+    let client = DiscordApiClient::new(token, state.endpoints.clone())?;
 
     // Validate token
-    let user = client
-        .get_current_user()
-        .await
-        .map_err(|e| format!("Failed to validate token: {}", e))?;
+    let user = client.get_current_user().await?;
 
     // Save client
     *state.client.lock().unwrap() = Some(client);
+    *state.user.lock().unwrap() = Some(user.clone());
 
     Ok(user)
 }
 
-/// Get quest list (via HTTP API /quests/@me endpoint)
-#[tauri::command]
-async fn get_quests(state: State<'_, AppState>) -> Result<serde_json::Value, String> {
-    let client = {
-        let guard = state.client.lock().unwrap();
-        guard
-            .as_ref()
-            .ok_or_else(|| "Not logged in".to_string())?
-            .clone()
-    };
+/// The credential vault, unless there is no app data dir to keep it in
+fn credential_vault(state: &AppState) -> Result<Arc<CredentialVault>, CommandError> {
+    state
+        .vault
+        .clone()
+        .ok_or_else(|| CommandError::Other("No app data dir, the credential vault is unavailable".to_string()))
+}
 
-    let quests = client
-        .get_quests_raw()
+/// Run `f` on a blocking thread; vault calls wait on its locks or the disk, and
+/// deriving the key takes a noticeable moment
+async fn with_vault<T: Send + 'static>(
+    state: &AppState,
+    f: impl FnOnce(&CredentialVault) -> anyhow::Result<T> + Send + 'static,
+) -> Result<T, CommandError> {
+    let vault = credential_vault(state)?;
+    tauri::async_runtime::spawn_blocking(move || f(&vault))
         .await
-        .map_err(|e| format!("Failed to get quest list: {}", e))?;
+        .map_err(|e| CommandError::Other(e.to_string()))?
+        .map_err(CommandError::from)
+}
 
-    // Return the "quests" array directly
-    Ok(quests.get("quests").cloned().unwrap_or(serde_json::Value::Array(vec![])))
+/// Whether a vault exists, whether it is unlocked and its auto-lock time
+#[tauri::command]
+async fn get_vault_status(state: State<'_, AppState>) -> Result<VaultStatus, CommandError> {
+    with_vault(&state, |vault| Ok(vault.status())).await
 }
 
-/// Start video quest
+/// Save the logged-in account to the credential vault
+///
+/// `passphrase` is needed when the vault is locked or doesn't exist yet; an
+/// unlocked vault is re-encrypted with the key it was unlocked with, and a
+/// passphrase passed then is refused.
 #[tauri::command]
-async fn start_video_quest(
-    quest_id: String,
-    seconds_needed: u32,
-    initial_progress: f64,
-    speed_multiplier: f64,
-    heartbeat_interval: u64,
+async fn save_to_vault(
+    passphrase: Option<String>,
     state: State<'_, AppState>,
-    app_handle: tauri::AppHandle,
-) -> Result<(), String> {
-    // Stop current quest (if any)
-    stop_quest_internal(&state).await;
+) -> Result<Vec<ExtractedAccount>, CommandError> {
+    let token = logged_in_client(&state)?.get_token().clone();
+    let user = state.user.lock().unwrap().clone().ok_or(CommandError::NotLoggedIn)?;
+    let passphrase = passphrase.map(SecretString::from);
+    with_vault(&state, move |vault| vault.save(passphrase.as_ref(), user, token)).await
+}
 
-    let client = state.client.lock().unwrap();
-    let client = client
-        .as_ref()
-        .ok_or_else(|| "Not logged in".to_string())?
-        .clone();
+/// Unlock the credential vault; log in to one of the returned accounts with `select_account`
+#[tauri::command]
+async fn unlock_vault(passphrase: String, state: State<'_, AppState>) -> Result<Vec<ExtractedAccount>, CommandError> {
+    let passphrase = SecretString::from(passphrase);
+    with_vault(&state, move |vault| vault.unlock(&passphrase)).await
+}
 
-    // Create cancel channel
-    let (cancel_tx, cancel_rx) = tokio::sync::mpsc::channel::<()>(1);
+/// Drop the vault key and decrypted tokens from memory
+#[tauri::command]
+async fn lock_vault(state: State<'_, AppState>) -> Result<(), CommandError> {
+    with_vault(&state, |vault| {
+        vault.lock();
+        Ok(())
+    })
+    .await
+}
 
-    // Save quest state
-    *state.quest_state.lock().unwrap() = Some(QuestState {
-        quest_id: quest_id.clone(),
-        cancel_flag: cancel_tx,
-    });
+/// Lock the vault and delete its file
+#[tauri::command]
+async fn forget_vault(state: State<'_, AppState>) -> Result<(), CommandError> {
+    with_vault(&state, |vault| vault.forget()).await
+}
+
+/// Idle time in seconds after which an unlocked vault locks itself
+#[tauri::command]
+async fn set_vault_auto_lock(secs: u64, state: State<'_, AppState>) -> Result<(), CommandError> {
+    with_vault(&state, move |vault| vault.set_auto_lock(secs)).await
+}
+
+/// Get quest list (via HTTP API /quests/@me endpoint)
+#[tauri::command]
+async fn get_quests(state: State<'_, AppState>) -> Result<Vec<QuestView>, CommandError> {
+    let client = logged_in_client(&state)?;
+    Ok(client.get_quests().await?.into_iter().map(QuestView::from).collect())
+}
+
+/// Save `job` to the job store, register it and drive it on a background task
+///
+/// Progress is mirrored into the job store until the quest completes or is
+/// stopped; the run is removed from the registry when it ends, whatever the outcome.
+fn start_job(state: &AppState, app_handle: tauri::AppHandle, job: QuestJob) -> Result<(), CommandError> {
+    let client = logged_in_client(state)?;
+
+    let quest_id = job.quest_id.clone();
+    let kind = job.task.kind();
+
+    let run = state.jobs.save(job.clone());
+    let registry = state.quests.clone();
+    let (ticket, control_rx) = registry.start(&quest_id, kind);
+    let events = PersistingEvents::new(
+        TauriQuestEvents::new(app_handle),
+        state.jobs.clone(),
+        quest_id.clone(),
+        run,
+    );
 
-    // Run in background task
     tokio::spawn(async move {
-        let result = quest_completer::complete_video_quest(
-            &client,
-            quest_id,
-            seconds_needed,
-            initial_progress,
-            speed_multiplier,
-            heartbeat_interval,
-            app_handle.clone(),
-            cancel_rx,
-        )
-        .await;
+        let registered = RegisteredEvents::new(&events, &registry, &quest_id, ticket);
+        let result = dispatch::run_job(&client, &job, &registered, control_rx).await;
+        registry.finish(&quest_id, ticket);
 
+        // The completer has already reported the failure through `events`
         if let Err(e) = result {
-            let _ = app_handle.emit("quest-error", format!("Video quest failed: {}", e));
+            warn!("Quest {} ({}) failed: {}", quest_id, kind, e);
         }
     });
 
     Ok(())
 }
 
+/// Start a quest by id, letting the backend pick the completer from its task config
+///
+/// Enrolls first if needed. Returns the started job, or `None` if the quest
+/// was already completed.
+#[tauri::command]
+async fn run_quest(
+    quest_id: String,
+    speed_multiplier: Option<f64>,
+    heartbeat_interval: Option<u64>,
+    state: State<'_, AppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<Option<QuestJob>, CommandError> {
+    let client = logged_in_client(&state)?;
+    let defaults = RunOptions::default();
+    let options = RunOptions {
+        speed_multiplier: speed_multiplier.unwrap_or(defaults.speed_multiplier),
+        heartbeat_interval: heartbeat_interval.unwrap_or(defaults.heartbeat_interval),
+        stream_key: None,
+    };
+
+    match dispatch::prepare_quest(&client, &quest_id, &options).await? {
+        Prepared::AlreadyCompleted => {
+            info!("Quest {} is already completed", quest_id);
+            Ok(None)
+        }
+        Prepared::Run(job) => {
+            info!("Running quest {} as {} ({}s needed, {:.1}% done)", quest_id, job.task.kind(), job.seconds_needed, job.progress);
+            start_job(&state, app_handle, job.clone())?;
+            Ok(Some(job))
+        }
+    }
+}
+
+/// Start video quest
+#[tauri::command]
+async fn start_video_quest(
+    quest_id: String,
+    seconds_needed: u32,
+    initial_progress: f64,
+    speed_multiplier: f64,
+    heartbeat_interval: u64,
+    state: State<'_, AppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<(), CommandError> {
+    let task = JobTask::Video { speed_multiplier, heartbeat_interval };
+    start_job(&state, app_handle, QuestJob::new(quest_id, task, seconds_needed, initial_progress))
+}
+
 /// Start stream quest
 #[tauri::command]
 async fn start_stream_quest(
@@ -167,46 +311,9 @@ async fn start_stream_quest(
     initial_progress: f64,
     state: State<'_, AppState>,
     app_handle: tauri::AppHandle,
-) -> Result<(), String> {
-    // Stop current quest (if any)
-    stop_quest_internal(&state).await;
-
-    let client = {
-        let guard = state.client.lock().unwrap();
-        guard
-            .as_ref()
-            .ok_or_else(|| "Not logged in".to_string())?
-            .clone()
-    };
-
-    // Create cancel channel
-    let (cancel_tx, cancel_rx) = tokio::sync::mpsc::channel::<()>(1);
-
-    // Save quest state
-    *state.quest_state.lock().unwrap() = Some(QuestState {
-        quest_id: quest_id.clone(),
-        cancel_flag: cancel_tx,
-    });
-
-    // Run in background task
-    tokio::spawn(async move {
-        let result = quest_completer::complete_stream_quest(
-            &client,
-            quest_id,
-            stream_key,
-            seconds_needed,
-            initial_progress,
-            app_handle.clone(),
-            cancel_rx,
-        )
-        .await;
-
-        if let Err(e) = result {
-            let _ = app_handle.emit("quest-error", format!("Stream quest failed: {}", e));
-        }
-    });
-
-    Ok(())
+) -> Result<(), CommandError> {
+    let task = JobTask::Stream { stream_key };
+    start_job(&state, app_handle, QuestJob::new(quest_id, task, seconds_needed, initial_progress))
 }
 
 /// Start game quest via direct heartbeat (without running simulated game)
@@ -218,65 +325,222 @@ async fn start_game_heartbeat_quest(
     initial_progress: f64,
     state: State<'_, AppState>,
     app_handle: tauri::AppHandle,
-) -> Result<(), String> {
-    // Stop current quest (if any)
-    stop_quest_internal(&state).await;
-
-    let client = {
-        let guard = state.client.lock().unwrap();
-        guard
-            .as_ref()
-            .ok_or_else(|| "Not logged in".to_string())?
-            .clone()
+) -> Result<(), CommandError> {
+    let task = JobTask::Game { application_id };
+    start_job(&state, app_handle, QuestJob::new(quest_id, task, seconds_needed, initial_progress))
+}
+
+/// Jobs left over from a previous session (crash, restart, sleep) that can be resumed
+#[tauri::command]
+async fn list_resumable_jobs(state: State<'_, AppState>) -> Result<Vec<QuestJob>, CommandError> {
+    Ok(state
+        .jobs
+        .list()
+        .into_iter()
+        .filter(|job| !state.quests.is_running(&job.quest_id))
+        .collect())
+}
+
+/// Restart a stored job from its last confirmed progress
+#[tauri::command]
+async fn resume_job(
+    quest_id: String,
+    state: State<'_, AppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<QuestJob, CommandError> {
+    let job = state
+        .jobs
+        .get(&quest_id)
+        .ok_or_else(|| CommandError::Other(format!("No saved job for quest {}", quest_id)))?;
+
+    info!("Resuming quest {} ({}) at {:.1}%", quest_id, job.task.kind(), job.progress);
+    start_job(&state, app_handle, job.clone())?;
+    Ok(job)
+}
+
+/// Forget a stored job without running it
+#[tauri::command]
+async fn discard_job(quest_id: String, state: State<'_, AppState>) -> Result<(), CommandError> {
+    state.jobs.remove(&quest_id);
+    Ok(())
+}
+
+/// Stop one running quest; returns false if it wasn't running
+#[tauri::command]
+async fn stop_quest(quest_id: String, state: State<'_, AppState>) -> Result<bool, CommandError> {
+    let stopped = state.quests.stop(&quest_id);
+    if stopped {
+        info!("Quest {} stopped", quest_id);
+    }
+    Ok(stopped)
+}
+
+/// Pause a running quest, keeping its position; returns false if it isn't running or already paused
+#[tauri::command]
+async fn pause_quest(quest_id: String, state: State<'_, AppState>) -> Result<bool, CommandError> {
+    Ok(state.quests.pause(&quest_id))
+}
+
+/// Continue a paused quest where it left off; returns false if it isn't paused
+#[tauri::command]
+async fn resume_quest(quest_id: String, state: State<'_, AppState>) -> Result<bool, CommandError> {
+    Ok(state.quests.resume(&quest_id))
+}
+
+/// Stop every running quest; returns how many were stopped
+#[tauri::command]
+async fn stop_all_quests(state: State<'_, AppState>) -> Result<usize, CommandError> {
+    let stopped = state.quests.stop_all();
+    info!("Stopped {} quests", stopped);
+    Ok(stopped)
+}
+
+/// Quests currently running in the background
+#[tauri::command]
+async fn list_running_quests(state: State<'_, AppState>) -> Result<Vec<RunningQuestInfo>, CommandError> {
+    Ok(state.quests.list())
+}
+
+/// Latest log entries at `level` or more severe (default `info`), oldest first
+///
+/// `level` is one of `error`, `warn`, `info`, `debug` or `trace`.
+#[tauri::command]
+async fn get_recent_logs(
+    level: Option<String>,
+    limit: Option<usize>,
+    state: State<'_, AppState>,
+) -> Result<Vec<LogEntry>, CommandError> {
+    let level = match level {
+        Some(level) => level
+            .parse::<tracing::Level>()
+            .map_err(|_| CommandError::Other(format!("Unknown log level: {}", level)))?,
+        None => tracing::Level::INFO,
     };
+    Ok(state.logs.recent(level, limit))
+}
 
-    // Create cancel channel
-    let (cancel_tx, cancel_rx) = tokio::sync::mpsc::channel::<()>(1);
+/// Everything the backend has running, as returned by `get_backend_status`
+#[derive(Debug, Clone, serde::Serialize)]
+struct BackendStatus {
+    /// Logged-in account, if any
+    user: Option<DiscordUser>,
+    /// Quests running in the background, with their latest progress
+    running_quests: Vec<RunningQuestInfo>,
+    /// Activity set through Discord RPC, if connected
+    rpc_activity: Option<RpcActivityStatus>,
+    /// Simulated games launched and not stopped since
+    simulated_games: Vec<game_simulator::SimulatedGame>,
+}
 
-    // Save quest state
-    *state.quest_state.lock().unwrap() = Some(QuestState {
-        quest_id: quest_id.clone(),
-        cancel_flag: cancel_tx,
-    });
+/// What the backend is doing right now, so a reloaded UI can reattach to it
+#[tauri::command]
+async fn get_backend_status(state: State<'_, AppState>) -> Result<BackendStatus, CommandError> {
+    let rpc_activity = get_discord_rpc_client()
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map(|session| session.status.clone());
+
+    Ok(BackendStatus {
+        user: state.user.lock().unwrap().clone(),
+        running_quests: state.quests.list(),
+        rpc_activity,
+        simulated_games: game_simulator::launched_games(),
+    })
+}
+
+/// Current queue contents, so the UI can rebuild its view after a reload
+#[tauri::command]
+async fn get_queue(state: State<'_, AppState>) -> Result<QueueSnapshot, CommandError> {
+    Ok(state.queue.snapshot())
+}
+
+/// Append quests to the queue
+#[tauri::command]
+async fn enqueue_quests(
+    quest_ids: Vec<String>,
+    state: State<'_, AppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<QueueSnapshot, CommandError> {
+    let snapshot = state.queue.enqueue(&quest_ids);
+    TauriQueueEvents::new(app_handle).changed(&snapshot);
+    Ok(snapshot)
+}
+
+/// Move the given quests to the front of the queue, in that order
+#[tauri::command]
+async fn reorder_queue(
+    quest_ids: Vec<String>,
+    state: State<'_, AppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<QueueSnapshot, CommandError> {
+    let snapshot = state.queue.reorder(&quest_ids);
+    TauriQueueEvents::new(app_handle).changed(&snapshot);
+    Ok(snapshot)
+}
+
+/// Remove a quest from the queue (the running one has to be stopped instead)
+#[tauri::command]
+async fn remove_from_queue(
+    quest_id: String,
+    state: State<'_, AppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<QueueSnapshot, CommandError> {
+    let snapshot = state.queue.remove(&quest_id).ok_or_else(|| {
+        CommandError::Other(format!("Quest {} is not queued or is currently running", quest_id))
+    })?;
+    TauriQueueEvents::new(app_handle).changed(&snapshot);
+    Ok(snapshot)
+}
+
+/// Start working through the queue; returns false if it is already running
+#[tauri::command]
+async fn start_queue(
+    speed_multiplier: f64,
+    heartbeat_interval: u64,
+    state: State<'_, AppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<bool, CommandError> {
+    let client = logged_in_client(&state)?;
+    if !state.queue.try_start() {
+        return Ok(false);
+    }
+
+    let queue = state.queue.clone();
+    let registry = state.quests.clone();
+    let jobs = state.jobs.clone();
+    let options = RunOptions {
+        speed_multiplier,
+        heartbeat_interval,
+        stream_key: None,
+    };
 
-    // Run in background task
     tokio::spawn(async move {
-        let result = quest_completer::complete_game_quest_via_heartbeat(
+        queue::run_queue(
+            &queue,
             &client,
-            quest_id,
-            application_id,
-            seconds_needed,
-            initial_progress,
-            app_handle.clone(),
-            cancel_rx,
+            &registry,
+            &jobs,
+            &options,
+            &TauriQuestEvents::new(app_handle.clone()),
+            &TauriQueueEvents::new(app_handle),
         )
         .await;
-
-        if let Err(e) = result {
-            let _ = app_handle.emit("quest-error", format!("Game heartbeat quest failed: {}", e));
-        }
     });
 
-    Ok(())
+    Ok(true)
 }
 
-/// Stop current quest
+/// Stop the queue and the quest it is running; returns false if it wasn't running
 #[tauri::command]
-async fn stop_quest(state: State<'_, AppState>) -> Result<(), String> {
-    stop_quest_internal(&state).await;
-    Ok(())
-}
-
-async fn stop_quest_internal(state: &State<'_, AppState>) {
-    let quest = {
-        let mut quest_state = state.quest_state.lock().unwrap();
-        quest_state.take()
-    };
-    
-    if let Some(quest) = quest {
-        let _ = quest.cancel_flag.send(()).await;
-        println!("Quest stopped");
+async fn stop_queue(state: State<'_, AppState>) -> Result<bool, CommandError> {
+    if !state.queue.snapshot().running {
+        return Ok(false);
+    }
+    if let Some(quest_id) = state.queue.request_stop() {
+        state.quests.stop(&quest_id);
     }
+    Ok(true)
 }
 
 /// Create simulated game
@@ -285,9 +549,9 @@ async fn create_simulated_game(
     path: String,
     executable_name: String,
     app_id: String,
-) -> Result<(), String> {
+) -> Result<(), CommandError> {
     game_simulator::create_simulated_game(&path, &executable_name, &app_id)
-        .map_err(|e| format!("Failed to create simulated game: {}", e))
+        .map_err(|e| CommandError::Other(format!("Failed to create simulated game: {}", e)))
 }
 
 /// Run simulated game
@@ -297,63 +561,58 @@ async fn run_simulated_game(
     path: String,
     executable_name: String,
     app_id: String,
-) -> Result<(), String> {
+) -> Result<(), CommandError> {
     game_simulator::run_simulated_game(&name, &path, &executable_name, &app_id)
-        .map_err(|e| format!("Failed to run simulated game: {}", e))
+        .map_err(|e| CommandError::Other(format!("Failed to run simulated game: {}", e)))
 }
 
 /// Stop simulated game
 #[tauri::command]
-async fn stop_simulated_game(exec_name: String) -> Result<(), String> {
+async fn stop_simulated_game(exec_name: String) -> Result<(), CommandError> {
     game_simulator::stop_simulated_game(&exec_name)
-        .map_err(|e| format!("Failed to stop simulated game: {}", e))
+        .map_err(|e| CommandError::Other(format!("Failed to stop simulated game: {}", e)))
 }
 
 /// Get detectable games list
 #[tauri::command]
-async fn fetch_detectable_games(state: State<'_, AppState>) -> Result<Vec<DetectableGame>, String> {
-    let client = {
-        let guard = state.client.lock().unwrap();
-        guard
-            .as_ref()
-            .ok_or_else(|| "Not logged in".to_string())?
-            .clone()
-    };
+async fn fetch_detectable_games(state: State<'_, AppState>) -> Result<Vec<DetectableGame>, CommandError> {
+    let client = logged_in_client(&state)?;
 
-    let games = client
-        .fetch_detectable_games()
-        .await
-        .map_err(|e| format!("Failed to get games list: {}", e))?;
+    let games = client.fetch_detectable_games().await?;
 
     Ok(games)
 }
 
 /// Accept quest
 #[tauri::command]
-async fn accept_quest(quest_id: String, state: State<'_, AppState>) -> Result<serde_json::Value, String> {
-    let client = {
-        let guard = state.client.lock().unwrap();
-        guard
-            .as_ref()
-            .ok_or_else(|| "Not logged in".to_string())?
-            .clone()
-    };
+async fn accept_quest(quest_id: String, state: State<'_, AppState>) -> Result<serde_json::Value, CommandError> {
+    let client = logged_in_client(&state)?;
 
-    let result = client
-        .accept_quest(&quest_id)
-        .await
-        .map_err(|e| format!("Failed to accept quest: {}", e))?;
+    let result = client.accept_quest(&quest_id).await?;
 
     Ok(result)
 }
 
-mod rpc;
-mod runner;
+/// Activity shown through the Discord RPC connection, as reported by `get_backend_status`
+#[derive(Debug, Clone, serde::Serialize)]
+struct RpcActivityStatus {
+    app_id: String,
+    /// Activity JSON as passed to `connect_to_discord_rpc`
+    activity: serde_json::Value,
+    /// Unix timestamp (seconds) of when the connection came up
+    connected_at: u64,
+}
+
+/// A live Discord RPC connection and the activity it was set up with
+struct RpcSession {
+    client: rpc::Client,
+    status: RpcActivityStatus,
+}
 
 use once_cell::sync::OnceCell;
-static DISCORD_RPC_CLIENT: OnceCell<Mutex<Option<rpc::Client>>> = OnceCell::new();
+static DISCORD_RPC_CLIENT: OnceCell<Mutex<Option<RpcSession>>> = OnceCell::new();
 
-fn get_discord_rpc_client() -> &'static Mutex<Option<rpc::Client>> {
+fn get_discord_rpc_client() -> &'static Mutex<Option<RpcSession>> {
     DISCORD_RPC_CLIENT.get_or_init(|| Mutex::new(None))
 }
 
@@ -371,6 +630,7 @@ fn connect_to_discord_rpc(handle: tauri::AppHandle, activity_json: String, actio
     let connecting_payload = serde_json::json!({
         "app_id": activity.app_id,
     });
+    let activity_json_copy = activity_json.clone();
 
     // Clear existing client
     {
@@ -381,7 +641,7 @@ fn connect_to_discord_rpc(handle: tauri::AppHandle, activity_json: String, actio
     let task = tauri::async_runtime::spawn(async move {
         handle
             .emit(event_connecting, connecting_payload)
-            .unwrap_or_else(|e| eprintln!("Failed to emit event: {}", e));
+            .unwrap_or_else(|e| error!("Failed to emit event: {}", e));
 
         let client_result = runner::set_activity(activity_json).await;
             
@@ -392,44 +652,52 @@ fn connect_to_discord_rpc(handle: tauri::AppHandle, activity_json: String, actio
                 });
 
                 {
+                    let status = RpcActivityStatus {
+                        app_id: activity.app_id.clone(),
+                        activity: serde_json::from_str(&activity_json_copy).unwrap_or_default(),
+                        connected_at: std::time::SystemTime::now()
+                            .duration_since(std::time::UNIX_EPOCH)
+                            .map(|d| d.as_secs())
+                            .unwrap_or(0),
+                    };
                     let mut client_guard = get_discord_rpc_client().lock().unwrap();
-                    *client_guard = Some(client);
+                    *client_guard = Some(RpcSession { client, status });
                 }
 
                 handle
                     .emit(event_connected, connected_payload)
                     .unwrap_or_else(|e| {
-                        eprintln!("Failed to emit event: {}", e);
+                        error!("Failed to emit event: {}", e);
                     });
 
                 handle.listen(event_disconnect, move |_| {
-                    println!("Disconnecting from Discord RPC inner");
+                    debug!("Disconnecting from Discord RPC inner");
                     let _ = tauri::async_runtime::spawn(async move {
-                        let client_option = {
+                        let session = {
                             let mut client_guard = get_discord_rpc_client().lock().unwrap();
                             client_guard.take()
                         };
-                        if let Some(client) = client_option {
-                            client.discord.disconnect().await;
-                            println!("Disconnected from Discord RPC inner");
+                        if let Some(session) = session {
+                            session.client.discord.disconnect().await;
+                            debug!("Disconnected from Discord RPC inner");
                         }
                     });
                 });
             },
             Err(e) => {
-                println!("Failed to set activity: {}", e);
+                warn!("Failed to set activity: {}", e);
             }
         }
     });
 
     app.listen(event_disconnect, move |_| {
-        println!("Disconnecting from Discord RPC...");
+        info!("Disconnecting from Discord RPC...");
         task.abort();
     });
 }
 
 #[tauri::command]
-async fn open_in_explorer(path: String) -> Result<(), String> {
+async fn open_in_explorer(path: String) -> Result<(), CommandError> {
     #[cfg(target_os = "windows")]
     {
         let mut path = path.replace("/", "\\");
@@ -437,19 +705,19 @@ async fn open_in_explorer(path: String) -> Result<(), String> {
         if path.starts_with("\\\\?\\") {
             path = path[4..].to_string();
         }
-        println!("Opening explorer at: {}", path);
+        debug!("Opening explorer at: {}", path);
         std::process::Command::new("explorer")
             .arg(path)
             .spawn()
-            .map_err(|e| format!("Failed to open explorer: {}", e))?;
+            .map_err(|e| CommandError::Other(format!("Failed to open explorer: {}", e)))?;
     }
     #[cfg(target_os = "macos")]
     {
-        println!("Opening Finder at: {}", path);
+        debug!("Opening Finder at: {}", path);
         std::process::Command::new("open")
             .arg(&path)
             .spawn()
-            .map_err(|e| format!("Failed to open Finder: {}", e))?;
+            .map_err(|e| CommandError::Other(format!("Failed to open Finder: {}", e)))?;
     }
     #[cfg(not(any(target_os = "windows", target_os = "macos")))]
     {
@@ -463,49 +731,112 @@ pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_fs::init())
-        .manage(AppState {
-            client: Mutex::new(None),
-            quest_state: Mutex::new(None),
+        .setup(|app| {
+            // Console output is gone in release builds, so keep a log file under the app log dir
+            let log_file = app.path().app_log_dir().ok().and_then(|dir| RotatingFile::in_dir(&dir).ok());
+            let log_path = log_file.as_ref().map(|file| file.path().to_path_buf());
+            let logs = logging::install(tracing::Level::DEBUG, log_file);
+            info!("Logging to {:?}", log_path);
+
+            // Endpoints come from <app config dir>/endpoints.json and DQH_* env overrides
+            let config_dir = app.path().app_config_dir().ok();
+            let endpoints = DiscordEndpoints::load(config_dir.as_deref()).unwrap_or_else(|e| {
+                warn!("Failed to load endpoint config, using defaults: {}", e);
+                DiscordEndpoints::default()
+            });
+            info!("Using Discord API base: {}", endpoints.rest_url(""));
+
+            let data_dir = app.path().app_data_dir();
+            let jobs = Arc::new(match &data_dir {
+                Ok(dir) => JobStore::open(dir),
+                Err(e) => {
+                    warn!("No app data dir, quest jobs won't be persisted: {}", e);
+                    JobStore::in_memory()
+                }
+            });
+            tauri::async_runtime::spawn(jobs.clone().run_flusher());
+
+            // The vault is locked on start; the auto-lock task runs for the app's lifetime
+            let vault = data_dir.ok().map(|dir| Arc::new(CredentialVault::open(&dir)));
+            if let Some(vault) = &vault {
+                tauri::async_runtime::spawn(vault.clone().run_auto_lock());
+            }
+
+            app.manage(AppState {
+                client: Mutex::new(None),
+                user: Mutex::new(None),
+                accounts: AccountStore::new(),
+                vault,
+                quests: Arc::new(QuestRegistry::new()),
+                queue: Arc::new(QuestQueue::new()),
+                jobs,
+                endpoints,
+                logs,
+            });
+            Ok(())
         })
         .invoke_handler(tauri::generate_handler![
             auto_detect_token,
             set_token,
+            select_account,
+            get_vault_status,
+            save_to_vault,
+            unlock_vault,
+            lock_vault,
+            forget_vault,
+            set_vault_auto_lock,
             get_quests,
+            run_quest,
             start_video_quest,
             start_stream_quest,
             start_game_heartbeat_quest,
             stop_quest,
+            stop_all_quests,
+            pause_quest,
+            resume_quest,
+            list_running_quests,
+            get_backend_status,
+            get_recent_logs,
+            list_resumable_jobs,
+            resume_job,
+            discard_job,
+            get_queue,
+            enqueue_quests,
+            reorder_queue,
+            remove_from_queue,
+            start_queue,
+            stop_queue,
             create_simulated_game,
             run_simulated_game,
             stop_simulated_game,
             fetch_detectable_games,
             accept_quest,
             connect_to_discord_rpc,
-            open_in_explorer,
-            force_video_progress
+            open_in_explorer
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
-}
-
-/// Force update video progress (used for ensuring final progress is saved on stop)
-#[tauri::command]
-async fn force_video_progress(
-    quest_id: String,
-    timestamp: f64,
-    state: State<'_, AppState>,
-) -> Result<(), String> {
-    let client = {
-        let guard = state.client.lock().unwrap();
-        guard
-            .as_ref()
-            .ok_or_else(|| "Not logged in".to_string())?
-            .clone()
-    };
-
-    client.update_video_progress(&quest_id, timestamp)
-        .await
-        .map_err(|e| format!("Failed to force video progress: {}", e))?;
-
-    Ok(())
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app_handle, event| {
+            if let tauri::RunEvent::ExitRequested { api, .. } = event {
+                // Stop running quests and give them a moment to save their position and
+                // close their sessions; their jobs are kept for resuming on next start
+                let state = app_handle.state::<AppState>();
+                state.jobs.close();
+                // Keep the queue from starting its next item while the current one winds down
+                state.queue.request_stop();
+                let registry = state.quests.clone();
+                let stopped = registry.stop_all();
+                if stopped > 0 {
+                    info!("Stopping {} quests before exit", stopped);
+                    api.prevent_exit();
+                    let app_handle = app_handle.clone();
+                    tauri::async_runtime::spawn(async move {
+                        if tokio::time::timeout(SHUTDOWN_GRACE, registry.wait_finished()).await.is_err() {
+                            warn!("Quests did not finish in time, exiting anyway");
+                        }
+                        app_handle.exit(0);
+                    });
+                }
+            }
+        });
 }
@@ -0,0 +1,107 @@
+use quest_core::event_schema::{ErrorDetail, QuestEventContext, SessionClosedDetail, StoppedDetail};
+use quest_core::quest_events::{QuestEvent, QuestEvents};
+use quest_core::queue::{QueueEvents, QueueItem, QueueSnapshot};
+use serde::Serialize;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter};
+use tracing::debug;
+
+/// Forwards quest events to the frontend as Tauri events
+///
+/// Emits `quest-started`, `quest-progress`, `quest-complete`, `quest-stopped`,
+/// `quest-error`, `quest-retrying`, `quest-paused`, `quest-resumed`,
+/// `quest-verified`, `quest-verification-failed` and `quest-session-closed`,
+/// each as a [`quest_core::event_schema::QuestEventPayload`] for the run that
+/// last started.
+pub struct TauriQuestEvents {
+    app_handle: AppHandle,
+    context: Mutex<Option<QuestEventContext>>,
+}
+
+impl TauriQuestEvents {
+    pub fn new(app_handle: AppHandle) -> Self {
+        Self {
+            app_handle,
+            context: Mutex::new(None),
+        }
+    }
+
+    fn send<T: Serialize + Clone>(&self, event: &str, detail: T) {
+        let context = self.context.lock().unwrap();
+        let Some(context) = context.as_ref() else {
+            debug!("Dropping {} sent before any quest started", event);
+            return;
+        };
+        let _ = self.app_handle.emit(event, context.payload(detail));
+    }
+
+    fn record_progress(&self, progress: f64) {
+        if let Some(context) = self.context.lock().unwrap().as_ref() {
+            context.record_progress(progress);
+        }
+    }
+}
+
+impl QuestEvents for TauriQuestEvents {
+    fn emit(&self, event: QuestEvent) {
+        match event {
+            QuestEvent::Started(job) => {
+                *self.context.lock().unwrap() = Some(QuestEventContext::new(&job));
+                self.send("quest-started", ());
+            }
+            QuestEvent::Progress(progress) => {
+                self.record_progress(progress);
+                self.send("quest-progress", ());
+            }
+            QuestEvent::Completed => self.send("quest-complete", ()),
+            QuestEvent::Stopped(flushed_progress) => {
+                if let Some(progress) = flushed_progress {
+                    self.record_progress(progress);
+                }
+                self.send("quest-stopped", StoppedDetail { flushed_progress });
+            }
+            QuestEvent::Error(message) => self.send("quest-error", ErrorDetail { message }),
+            QuestEvent::Retrying(notice) => self.send("quest-retrying", notice),
+            QuestEvent::Paused => self.send("quest-paused", ()),
+            QuestEvent::Resumed => self.send("quest-resumed", ()),
+            QuestEvent::Verified(report) => self.send("quest-verified", report),
+            QuestEvent::VerificationFailed(report) => self.send("quest-verification-failed", report),
+            QuestEvent::SessionClosed { acknowledged } => {
+                self.send("quest-session-closed", SessionClosedDetail { acknowledged })
+            }
+        }
+    }
+}
+
+/// Forwards queue events to the frontend
+///
+/// Emits `queue-changed` (full snapshot), `queue-item-started`,
+/// `queue-item-finished` and `queue-finished`.
+#[derive(Clone)]
+pub struct TauriQueueEvents {
+    app_handle: AppHandle,
+}
+
+impl TauriQueueEvents {
+    pub fn new(app_handle: AppHandle) -> Self {
+        Self { app_handle }
+    }
+}
+
+impl QueueEvents for TauriQueueEvents {
+    fn changed(&self, snapshot: &QueueSnapshot) {
+        let _ = self.app_handle.emit("queue-changed", snapshot);
+    }
+
+    fn item_started(&self, quest_id: &str) {
+        let _ = self.app_handle.emit("queue-item-started", quest_id);
+    }
+
+    fn item_finished(&self, item: &QueueItem) {
+        let _ = self.app_handle.emit("queue-item-finished", item);
+    }
+
+    fn finished(&self, snapshot: &QueueSnapshot) {
+        let _ = self.app_handle.emit("queue-finished", snapshot);
+    }
+}
@@ -1,8 +1,10 @@
 use anyhow::{Context, Result};
 use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use quest_core::secret::SecretString;
 use regex::Regex;
 use std::fs;
 use std::path::PathBuf;
+use tracing::{debug, info, warn};
 
 // Windows-specific imports
 #[cfg(target_os = "windows")]
@@ -59,8 +61,8 @@ impl DiscordClient {
 }
 
 /// Auto-detect and extract Discord tokens (returns all unique tokens found)
-pub fn extract_tokens() -> Result<Vec<String>> {
-    println!("Starting token extraction...");
+pub fn extract_tokens() -> Result<Vec<SecretString>> {
+    info!("Starting token extraction...");
     let mut tokens = std::collections::HashSet::new();
     let clients = vec![
         DiscordClient::Stable,
@@ -69,24 +71,24 @@ pub fn extract_tokens() -> Result<Vec<String>> {
     ];
 
     for client in clients {
-        println!("Checking client: {:?}", client);
+        debug!("Checking client: {:?}", client);
         if let Ok(client_tokens) = try_extract_from_client(&client) {
-            println!("Found {} tokens in {:?}", client_tokens.len(), client);
+            info!("Found {} tokens in {:?}", client_tokens.len(), client);
             for token in client_tokens {
                 tokens.insert(token);
             }
         } else {
-             println!("Failed to extract from {:?}", client);
+             warn!("Failed to extract from {:?}", client);
         }
     }
     
-    println!("Total unique tokens found: {}", tokens.len());
+    info!("Total unique tokens found: {}", tokens.len());
 
     if tokens.is_empty() {
         anyhow::bail!("Could not find tokens in any Discord client")
     }
 
-    Ok(tokens.into_iter().collect())
+    Ok(tokens.into_iter().map(SecretString::from).collect())
 }
 
 #[cfg(target_os = "windows")]
@@ -193,7 +195,7 @@ fn try_extract_from_client(client: &DiscordClient) -> Result<Vec<String>> {
         anyhow::bail!("Discord path does not exist: {:?}", discord_path);
     }
     
-    println!("Checking Discord path: {:?}", discord_path);
+    debug!("Checking Discord path: {:?}", discord_path);
     
     // Get the master key from macOS Keychain
     let master_key = get_master_key_from_keychain(client)?;
@@ -235,18 +237,18 @@ fn get_master_key_from_keychain(client: &DiscordClient) -> Result<Vec<u8>> {
     let service_name = client.safe_storage_name();
     let account_name = client.keychain_account();
     
-    println!("Looking for Keychain item: service='{}', account='{}'", service_name, account_name);
+    debug!("Looking for Keychain item: service='{}', account='{}'", service_name, account_name);
     
     let raw_password: Vec<u8>;
     
     // First try using the security-framework crate
     match get_generic_password(service_name, account_name) {
         Ok(password) => {
-            println!("Got password from Keychain using security-framework ({} bytes)", password.len());
+            debug!("Got password from Keychain using security-framework ({} bytes)", password.len());
             raw_password = password.to_vec();
         }
         Err(e) => {
-            println!("security-framework failed: {:?}, trying security command", e);
+            warn!("security-framework failed: {:?}, trying security command", e);
             
             // Fallback: Use the `security` command line tool
             let output = Command::new("security")
@@ -257,7 +259,7 @@ fn get_master_key_from_keychain(client: &DiscordClient) -> Result<Vec<u8>> {
             if output.status.success() {
                 let password_str = String::from_utf8_lossy(&output.stdout);
                 let password = password_str.trim();
-                println!("Got password from Keychain using security CLI ({} bytes)", password.len());
+                debug!("Got password from Keychain using security CLI ({} bytes)", password.len());
                 raw_password = password.as_bytes().to_vec();
             } else {
                 anyhow::bail!(
@@ -281,7 +283,7 @@ fn get_master_key_from_keychain(client: &DiscordClient) -> Result<Vec<u8>> {
     
     pbkdf2_hmac::<Sha1>(&raw_password, salt, iterations, &mut derived_key);
     
-    println!("Derived key using PBKDF2 (16 bytes): {:02x?}", &derived_key[..8]);
+    debug!("Derived key using PBKDF2 (16 bytes)");
     
     // For AES-256-GCM we need 32 bytes, but Chromium on macOS uses AES-128-CBC
     // Let's try with the 16-byte key first by padding it
@@ -1,6 +1,60 @@
-import { invoke } from '@tauri-apps/api/core'
+import { invoke, type InvokeArgs } from '@tauri-apps/api/core'
 import { listen } from '@tauri-apps/api/event'
 
+// Errors returned by backend commands, tagged by a stable `kind`
+export type BackendErrorKind =
+  | 'unauthorized'
+  | 'rate_limited'
+  | 'not_found'
+  | 'forbidden'
+  | 'discord_error'
+  | 'transport'
+  | 'decode'
+  | 'not_logged_in'
+  | 'other'
+
+export interface BackendErrorPayload {
+  kind: BackendErrorKind
+  message: string
+  retry_after?: number
+  global?: boolean
+  status?: number
+  code?: number | null
+}
+
+export class BackendError extends Error {
+  readonly kind: BackendErrorKind
+  readonly payload: BackendErrorPayload
+
+  constructor(payload: BackendErrorPayload) {
+    super(payload.message)
+    this.name = 'BackendError'
+    this.kind = payload.kind
+    this.payload = payload
+  }
+
+  // Keeps `${e}` and template interpolation showing just the message
+  toString(): string {
+    return this.message
+  }
+}
+
+function toBackendError(e: unknown): BackendError {
+  if (e instanceof BackendError) return e
+  if (e && typeof e === 'object' && 'kind' in e && 'message' in e) {
+    return new BackendError(e as BackendErrorPayload)
+  }
+  return new BackendError({ kind: 'other', message: String(e) })
+}
+
+async function call<T>(cmd: string, args?: InvokeArgs): Promise<T> {
+  try {
+    return await invoke<T>(cmd, args)
+  } catch (e) {
+    throw toBackendError(e)
+  }
+}
+
 export interface DiscordUser {
   id: string
   username: string
@@ -9,45 +63,73 @@ export interface DiscordUser {
   global_name: string | null
 }
 
+// Typed on the backend (quest_core::models::Quest) but kept in the /quests/@me shape;
+// fields not listed here are passed through unchanged
+export interface QuestTask {
+  type?: string
+  target: number
+}
+
+export interface QuestTaskConfig {
+  tasks: Record<string, QuestTask>
+}
+
+export interface QuestReward {
+  type?: number
+  messages: {
+    name: string
+  }
+  asset?: string
+}
+
+export interface QuestRewardView {
+  name: string
+  asset_url: string | null
+}
+
 export interface Quest {
   id: string
   config: {
     messages: {
       quest_name: string
       game_title?: string
+      game_publisher?: string
     }
     rewards_config?: {
-      rewards: Array<{
-        messages: {
-          name: string
-        }
-        asset?: string
-      }>
+      rewards: QuestReward[]
     }
     stream_duration_requirement_minutes?: number
-    task_config?: {
-      tasks?: Record<string, { target?: number }>
-    }
-    task_config_v2?: {
-      tasks?: Record<string, { target?: number }>
-    }
+    task_config?: QuestTaskConfig
+    task_config_v2?: QuestTaskConfig
     application?: {
       id: string
       name: string
-      link: string
+      link?: string
       icon?: string
     }
     assets?: {
       hero?: string
+      hero_video?: string
+      game_tile?: string
+      logotype?: string
     }
+    starts_at?: string
     expires_at?: string
   }
   user_status: {
-    progress?: Record<string, { value?: number }>
-    completed_at?: string | null
-    claimed_at?: string | null
-    enrolled_at?: string | null
+    // Keyed by task name; only tasks with some progress are present
+    progress: Record<string, { value: number }>
+    completed_at: string | null
+    claimed_at: string | null
+    enrolled_at: string | null
   } | null
+  // Derived by the backend
+  // Percentage done per task, keyed like the task config
+  progress: Record<string, number>
+  hero_url: string | null
+  icon_url: string | null
+  // Same order as config.rewards_config.rewards
+  rewards: QuestRewardView[]
 }
 
 export interface DetectableGame {
@@ -60,27 +142,75 @@ export interface DetectableGame {
 }
 
 // Auth commands
+// The token stays in the backend; log in with selectAccount(handle)
 export interface ExtractedAccount {
-  token: string
+  handle: string
   user: DiscordUser
 }
 
 export async function autoDetectToken(): Promise<ExtractedAccount[]> {
-  return await invoke('auto_detect_token')
+  return await call('auto_detect_token')
+}
+
+export async function selectAccount(handle: string): Promise<DiscordUser> {
+  return await call('select_account', { handle })
 }
 
 export async function setToken(token: string): Promise<DiscordUser> {
-  return await invoke('set_token', { token })
+  return await call('set_token', { token })
+}
+
+// Credential vault: manually entered tokens, encrypted with a passphrase in the app data dir
+export interface VaultStatus {
+  exists: boolean
+  unlocked: boolean
+  auto_lock_secs: number
+}
+
+export async function getVaultStatus(): Promise<VaultStatus> {
+  return await call('get_vault_status')
+}
+
+// Saves the logged-in account; the passphrase is only needed while the vault is locked or new
+export async function saveToVault(passphrase?: string): Promise<ExtractedAccount[]> {
+  return await call('save_to_vault', { passphrase })
+}
+
+// Accounts are returned by handle, like autoDetectToken; log in with selectAccount(handle)
+export async function unlockVault(passphrase: string): Promise<ExtractedAccount[]> {
+  return await call('unlock_vault', { passphrase })
+}
+
+export async function lockVault(): Promise<void> {
+  return await call('lock_vault')
+}
+
+export async function forgetVault(): Promise<void> {
+  return await call('forget_vault')
+}
+
+export async function setVaultAutoLock(secs: number): Promise<void> {
+  return await call('set_vault_auto_lock', { secs })
 }
 
 // RPC commands
 export function connectToDiscordRpc(activityJson: string, action: string = 'connect'): Promise<void> {
-  return invoke('connect_to_discord_rpc', { activity_json: activityJson, action })
+  return call('connect_to_discord_rpc', { activity_json: activityJson, action })
 }
 
 // User status commands
 export async function getQuests(): Promise<Quest[]> {
-  return await invoke('get_quests')
+  return await call('get_quests')
+}
+
+// Start a quest by id; the backend picks the completer and target from the quest's
+// task config and enrolls first if needed. Resolves to null if it was already completed.
+export async function runQuest(
+  questId: string,
+  speedMultiplier: number,
+  heartbeatInterval: number
+): Promise<QuestJob | null> {
+  return await call('run_quest', { questId, speedMultiplier, heartbeatInterval })
 }
 
 export async function startVideoQuest(
@@ -90,7 +220,7 @@ export async function startVideoQuest(
   speedMultiplier: number,
   heartbeatInterval: number
 ): Promise<void> {
-  return await invoke('start_video_quest', {
+  return await call('start_video_quest', {
     questId,
     secondsNeeded,
     initialProgress,
@@ -105,7 +235,7 @@ export async function startStreamQuest(
   secondsNeeded: number,
   initialProgress: number
 ): Promise<void> {
-  return await invoke('start_stream_quest', {
+  return await call('start_stream_quest', {
     questId,
     streamKey,
     secondsNeeded,
@@ -113,8 +243,86 @@ export async function startStreamQuest(
   })
 }
 
-export async function stopQuest(): Promise<void> {
-  return await invoke('stop_quest')
+// A quest running in the backend (several can run side by side)
+export interface RunningQuestInfo {
+  quest_id: string
+  kind: 'video' | 'stream' | 'game'
+  started_at: number
+  paused: boolean
+  // Latest percentage confirmed by Discord
+  progress: number
+  // 0 until the run has started
+  seconds_needed: number
+}
+
+// Returns false if the quest wasn't running
+export async function stopQuest(questId: string): Promise<boolean> {
+  return await call('stop_quest', { questId })
+}
+
+// Keeps the quest's position; returns false if it isn't running or already paused
+export async function pauseQuest(questId: string): Promise<boolean> {
+  return await call('pause_quest', { questId })
+}
+
+// Returns false if the quest isn't paused
+export async function resumeQuest(questId: string): Promise<boolean> {
+  return await call('resume_quest', { questId })
+}
+
+// Returns how many quests were stopped
+export async function stopAllQuests(): Promise<number> {
+  return await call('stop_all_quests')
+}
+
+export async function listRunningQuests(): Promise<RunningQuestInfo[]> {
+  return await call('list_running_quests')
+}
+
+// Simulated game launched by the backend and not stopped since
+export interface SimulatedGame {
+  name: string
+  path: string
+  executable_name: string
+  app_id: string
+  started_at: number
+}
+
+export interface RpcActivityStatus {
+  app_id: string
+  // Activity JSON as passed to connectToDiscordRpc
+  activity: Record<string, unknown>
+  connected_at: number
+}
+
+// Everything the backend has running, for reattaching after a webview reload
+export interface BackendStatus {
+  user: DiscordUser | null
+  running_quests: RunningQuestInfo[]
+  rpc_activity: RpcActivityStatus | null
+  simulated_games: SimulatedGame[]
+}
+
+export async function getBackendStatus(): Promise<BackendStatus> {
+  return await call('get_backend_status')
+}
+
+export type LogLevel = 'error' | 'warn' | 'info' | 'debug' | 'trace'
+
+// One backend log line
+export interface LogEntry {
+  timestamp_ms: number
+  // Upper case, e.g. 'INFO'
+  level: string
+  target: string
+  // Enclosing spans, e.g. 'quest{quest_id=1 kind=video}:request{action=...}'
+  spans: string
+  message: string
+}
+
+// Latest entries at `level` or more severe (default 'info'), oldest first
+export async function getRecentLogs(level?: LogLevel, limit?: number): Promise<LogEntry[]> {
+  return await call('get_recent_logs', { level, limit })
 }
 
 export async function startGameHeartbeatQuest(
@@ -123,7 +331,7 @@ export async function startGameHeartbeatQuest(
   secondsNeeded: number,
   initialProgress: number
 ): Promise<void> {
-  return await invoke('start_game_heartbeat_quest', {
+  return await call('start_game_heartbeat_quest', {
     questId,
     applicationId,
     secondsNeeded,
@@ -137,7 +345,7 @@ export async function createSimulatedGame(
   executableName: string,
   appId: string
 ): Promise<void> {
-  return await invoke('create_simulated_game', {
+  return await call('create_simulated_game', {
     path,
     executableName,
     appId
@@ -150,7 +358,7 @@ export async function runSimulatedGame(
   executableName: string,
   appId: string
 ): Promise<void> {
-  return await invoke('run_simulated_game', {
+  return await call('run_simulated_game', {
     name,
     path,
     executableName,
@@ -159,36 +367,193 @@ export async function runSimulatedGame(
 }
 
 export async function stopSimulatedGame(execName: string): Promise<void> {
-  return await invoke('stop_simulated_game', { execName })
+  return await call('stop_simulated_game', { execName })
 }
 
 export async function fetchDetectableGames(): Promise<DetectableGame[]> {
-  return await invoke('fetch_detectable_games')
+  return await call('fetch_detectable_games')
 }
 
 export async function acceptQuest(questId: string): Promise<void> {
-  return await invoke('accept_quest', { questId })
+  return await call('accept_quest', { questId })
+}
+
+// Quest jobs persisted by the backend, resumable after a restart
+export type JobTask =
+  | { kind: 'video'; speed_multiplier: number; heartbeat_interval: number }
+  | { kind: 'stream'; stream_key: string }
+  | { kind: 'game'; application_id: string }
+
+export type QuestJob = JobTask & {
+  quest_id: string
+  seconds_needed: number
+  // Last progress percentage confirmed by Discord
+  progress: number
+  updated_at: number
+}
+
+export async function listResumableJobs(): Promise<QuestJob[]> {
+  return await call('list_resumable_jobs')
+}
+
+export async function resumeJob(questId: string): Promise<QuestJob> {
+  return await call('resume_job', { questId })
+}
+
+export async function discardJob(questId: string): Promise<void> {
+  return await call('discard_job', { questId })
+}
+
+// Backend quest queue (runs enrolled quests one after another)
+export type QueueItemStatus = 'pending' | 'running' | 'completed' | 'failed' | 'stopped'
+
+export interface QueueItem {
+  quest_id: string
+  status: QueueItemStatus
+  error: string | null
+}
+
+export interface QueueSnapshot {
+  items: QueueItem[]
+  running: boolean
+  current: string | null
+}
+
+export async function getQueue(): Promise<QueueSnapshot> {
+  return await call('get_queue')
+}
+
+export async function enqueueQuests(questIds: string[]): Promise<QueueSnapshot> {
+  return await call('enqueue_quests', { questIds })
+}
+
+// Moves the given quests to the front, in that order
+export async function reorderQueue(questIds: string[]): Promise<QueueSnapshot> {
+  return await call('reorder_queue', { questIds })
+}
+
+export async function removeFromQueue(questId: string): Promise<QueueSnapshot> {
+  return await call('remove_from_queue', { questId })
+}
+
+// Returns false if the queue is already running
+export async function startQueue(speedMultiplier: number, heartbeatInterval: number): Promise<boolean> {
+  return await call('start_queue', { speedMultiplier, heartbeatInterval })
+}
+
+export async function stopQueue(): Promise<boolean> {
+  return await call('stop_queue')
 }
 
 // Event listeners
-export function onQuestProgress(callback: (progress: number) => void) {
-  return listen<number>('quest-progress', (event) => {
+// Quest events (schema version 1, see quest_core::event_schema).
+// Every quest event carries the same envelope; `detail` depends on the event.
+export const QUEST_EVENT_SCHEMA_VERSION = 1
+
+export interface QuestEvent<D = null> {
+  version: number
+  // Increases with every quest event, across quests
+  seq: number
+  quest_id: string
+  task_type: 'video' | 'stream' | 'game'
+  // Percentage (0-100) as last confirmed by Discord
+  progress: number
+  seconds_done: number
+  seconds_needed: number
+  // Estimated real seconds until the target is reached
+  eta_secs: number
+  detail: D
+}
+
+// Detail of `quest-retrying`: a request failed transiently and will be retried
+export interface QuestRetryNotice {
+  attempt: number
+  max_attempts: number
+  delay_secs: number
+  message: string
+}
+
+// Detail of `quest-verified` / `quest-verification-failed`: what Discord
+// had recorded after the completer reported the quest done
+export interface QuestVerificationReport {
+  quest_id: string
+  verified: boolean
+  completed_at: string | null
+  progress: number | null
+  attempts: number
+  error: string | null
+}
+
+function listenQuestEvent<D>(name: string, callback: (event: QuestEvent<D>) => void) {
+  return listen<QuestEvent<D>>(name, (event) => {
+    if (event.payload.version !== QUEST_EVENT_SCHEMA_VERSION) {
+      console.warn(`Unexpected ${name} schema version ${event.payload.version}`)
+    }
     callback(event.payload)
   })
 }
 
-export function onQuestComplete(callback: () => void) {
-  return listen('quest-complete', () => {
-    callback()
+export function onQuestStarted(callback: (event: QuestEvent) => void) {
+  return listenQuestEvent('quest-started', callback)
+}
+
+export function onQuestProgress(callback: (event: QuestEvent) => void) {
+  return listenQuestEvent('quest-progress', callback)
+}
+
+export function onQuestComplete(callback: (event: QuestEvent) => void) {
+  return listenQuestEvent('quest-complete', callback)
+}
+
+// `flushed_progress` is what Discord confirmed for the position saved on stop
+export function onQuestStopped(callback: (event: QuestEvent<{ flushed_progress: number | null }>) => void) {
+  return listenQuestEvent('quest-stopped', callback)
+}
+
+export function onQuestError(callback: (event: QuestEvent<{ message: string }>) => void) {
+  return listenQuestEvent('quest-error', callback)
+}
+
+export function onQuestRetrying(callback: (event: QuestEvent<QuestRetryNotice>) => void) {
+  return listenQuestEvent('quest-retrying', callback)
+}
+
+export function onQuestPaused(callback: (event: QuestEvent) => void) {
+  return listenQuestEvent('quest-paused', callback)
+}
+
+export function onQuestResumed(callback: (event: QuestEvent) => void) {
+  return listenQuestEvent('quest-resumed', callback)
+}
+
+export function onQuestVerified(callback: (event: QuestEvent<QuestVerificationReport>) => void) {
+  return listenQuestEvent('quest-verified', callback)
+}
+
+export function onQuestVerificationFailed(callback: (event: QuestEvent<QuestVerificationReport>) => void) {
+  return listenQuestEvent('quest-verification-failed', callback)
+}
+
+// `acknowledged`: whether Discord accepted the terminal heartbeat sent when
+// a game quest completes or is stopped
+export function onQuestSessionClosed(callback: (event: QuestEvent<{ acknowledged: boolean }>) => void) {
+  return listenQuestEvent('quest-session-closed', callback)
+}
+
+export function onQueueChanged(callback: (snapshot: QueueSnapshot) => void) {
+  return listen<QueueSnapshot>('queue-changed', (event) => {
+    callback(event.payload)
   })
 }
 
-export function onQuestError(callback: (error: string) => void) {
-  return listen<string>('quest-error', (event) => {
+export function onQueueItemFinished(callback: (item: QueueItem) => void) {
+  return listen<QueueItem>('queue-item-finished', (event) => {
     callback(event.payload)
   })
 }
 
-export async function forceVideoProgress(questId: string, timestamp: number): Promise<void> {
-  return await invoke('force_video_progress', { questId, timestamp })
+export function onQueueFinished(callback: (snapshot: QueueSnapshot) => void) {
+  return listen<QueueSnapshot>('queue-finished', (event) => {
+    callback(event.payload)
+  })
 }
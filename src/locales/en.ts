@@ -53,6 +53,11 @@ export default {
         discord_rewards: "Discord Rewards",
         active_progress: "Active Quest Progress",
         no_active: "No active quest",
+        retrying: "Request failed, retrying in {delay}s ({attempt}/{max})",
+        verification_failed: "Discord has not recorded the completion of \"{name}\" (progress {progress}%)",
+        pause: "Pause",
+        resume: "Resume",
+        paused: "Paused",
         up_next: "Up Next",
         submitted: "Submitted",
         pending: "Local Pending",
@@ -105,6 +110,10 @@ export default {
         cache: "Cache",
         cache_desc: "The application uses the Documents folder to store some cache files (e.g. simulated games).",
         open_cache_dir: "Open Cache Directory",
+        logs: "Logs",
+        logs_desc: "Copy recent log messages to attach them to a bug report. The full log is also saved in the app's log folder.",
+        log_level: "Level",
+        copy_logs: "Copy Logs",
         about: "About",
         about_desc: "A tool to help complete Discord quests automatically.",
         about_warning: "Warning: Using this tool may violate Discord's Terms of Service. Use at your own risk.",
@@ -160,6 +169,26 @@ export default {
         start_all_play_desc: "Run {count} Play quests one by one? Each quest will start after the previous one completes.",
         cancel: "Cancel",
         accept: "Accept",
-        start: "Start"
+        start: "Start",
+        resume_jobs_title: "Resume Interrupted Quests",
+        resume_jobs_desc: "{count} quests were still running when the app closed. Resume the most recent one from its saved progress?",
+        discard: "Discard",
+        resume: "Resume"
+    },
+    vault: {
+        title: "Credential Vault",
+        desc: "Keep manually entered tokens on this computer, encrypted with a passphrase, so they don't have to be pasted again on every launch.",
+        passphrase: "Passphrase",
+        save: "Save Current Account",
+        unlock: "Unlock Vault",
+        unlock_prompt: "Unlock your saved accounts",
+        lock: "Lock",
+        forget: "Forget Vault",
+        status_none: "No accounts saved",
+        status_locked: "Locked",
+        status_unlocked: "Unlocked",
+        auto_lock: "Lock after idle (minutes)",
+        forget_title: "Forget the Vault?",
+        forget_desc: "All saved accounts will be deleted from this computer. This can't be undone."
     }
 }
@@ -53,6 +53,11 @@ export default {
         discord_rewards: "Recompensas de Discord",
         active_progress: "Progreso de misión activa",
         no_active: "Sin misión activa",
+        retrying: "La solicitud falló, reintentando en {delay}s ({attempt}/{max})",
+        verification_failed: "Discord no ha registrado la finalización de \"{name}\" (progreso {progress}%)",
+        pause: "Pausar",
+        resume: "Reanudar",
+        paused: "En pausa",
         up_next: "Siguiente",
         submitted: "Enviado",
         pending: "Pendiente",
@@ -106,6 +111,10 @@ export default {
         cache: "Caché",
         cache_desc: "La aplicación utiliza la carpeta Documentos para almacenar algunos archivos de caché (ej. juegos simulados).",
         open_cache_dir: "Abrir carpeta de caché",
+        logs: "Registros",
+        logs_desc: "Copia los mensajes de registro recientes para adjuntarlos a un informe de error. El registro completo también se guarda en la carpeta de registros de la aplicación.",
+        log_level: "Nivel",
+        copy_logs: "Copiar registros",
         about_desc: "Una herramienta para ayudar a completar misiones de Discord automáticamente.",
         about_warning: "Advertencia: El uso de esta herramienta puede violar los Términos de Servicio de Discord. Úsala bajo tu propio riesgo.",
         feedback: "Reportar problema",
@@ -160,6 +169,26 @@ export default {
         start_all_play_desc: "¿Ejecutar {count} misiones Play una por una? La siguiente comenzará al terminar la anterior.",
         cancel: "Cancelar",
         accept: "Aceptar",
-        start: "Iniciar"
+        start: "Iniciar",
+        resume_jobs_title: "Reanudar misiones interrumpidas",
+        resume_jobs_desc: "{count} misiones seguían en curso cuando se cerró la app. ¿Reanudar la más reciente desde su progreso guardado?",
+        discard: "Descartar",
+        resume: "Reanudar"
+    },
+    vault: {
+        title: "Bóveda de credenciales",
+        desc: "Guarda en este equipo los tokens introducidos manualmente, cifrados con una frase de contraseña, para no tener que pegarlos en cada inicio.",
+        passphrase: "Frase de contraseña",
+        save: "Guardar cuenta actual",
+        unlock: "Desbloquear bóveda",
+        unlock_prompt: "Desbloquea tus cuentas guardadas",
+        lock: "Bloquear",
+        forget: "Olvidar bóveda",
+        status_none: "No hay cuentas guardadas",
+        status_locked: "Bloqueada",
+        status_unlocked: "Desbloqueada",
+        auto_lock: "Bloquear tras inactividad (minutos)",
+        forget_title: "¿Olvidar la bóveda?",
+        forget_desc: "Todas las cuentas guardadas se eliminarán de este equipo. Esta acción no se puede deshacer."
     }
 }
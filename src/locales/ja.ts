@@ -53,6 +53,11 @@ export default {
         discord_rewards: "Discord報酬",
         active_progress: "アクティブなクエスト進捗",
         no_active: "アクティブなクエストなし",
+        retrying: "リクエストに失敗しました。{delay}秒後に再試行します ({attempt}/{max})",
+        verification_failed: "Discord は「{name}」の完了を記録していません（進行状況 {progress}%）",
+        pause: "一時停止",
+        resume: "再開",
+        paused: "一時停止中",
         up_next: "次のクエスト",
         submitted: "送信済み",
         pending: "保留中"
@@ -106,6 +111,10 @@ export default {
         cache: "キャッシュ",
         cache_desc: "アプリケーションはドキュメントフォルダを使用して、一部のキャッシュファイル（シミュレーションゲームなど）を保存します。",
         open_cache_dir: "キャッシュディレクトリを開く",
+        logs: "ログ",
+        logs_desc: "最近のログをコピーして、バグ報告に添付できます。完全なログはアプリのログフォルダにも保存されます。",
+        log_level: "レベル",
+        copy_logs: "ログをコピー",
         about_desc: "Discordクエストを自動的に完了するためのツール。",
         about_warning: "警告：このツールの使用はDiscordの利用規約に違反する可能性があります。自己責任でご使用ください。",
         feedback: "問題を報告",
@@ -160,6 +169,26 @@ export default {
         start_all_play_desc: "{count}件のプレイクエストを順番に実行しますか？1つ完了すると次が自動で開始されます。",
         cancel: "キャンセル",
         accept: "受け入れる",
-        start: "開始"
+        start: "開始",
+        resume_jobs_title: "中断されたクエストを再開",
+        resume_jobs_desc: "アプリ終了時に{count}件のクエストが実行中でした。最新のクエストを保存された進捗から再開しますか？",
+        discard: "破棄",
+        resume: "再開"
+    },
+    vault: {
+        title: "認証情報ボールト",
+        desc: "手動で入力したトークンをパスフレーズで暗号化してこのコンピューターに保存し、起動のたびに貼り付ける手間を省きます。",
+        passphrase: "パスフレーズ",
+        save: "現在のアカウントを保存",
+        unlock: "ボールトのロックを解除",
+        unlock_prompt: "保存したアカウントのロックを解除",
+        lock: "ロック",
+        forget: "ボールトを削除",
+        status_none: "保存されたアカウントはありません",
+        status_locked: "ロック中",
+        status_unlocked: "ロック解除済み",
+        auto_lock: "自動ロックまでの待機時間（分）",
+        forget_title: "ボールトを削除しますか？",
+        forget_desc: "保存されたすべてのアカウントがこのコンピューターから削除されます。この操作は元に戻せません。"
     }
 }
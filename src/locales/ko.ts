@@ -53,6 +53,11 @@ export default {
         discord_rewards: "Discord 보상",
         active_progress: "현재 진행 상황",
         no_active: "진행 중인 퀘스트 없음",
+        retrying: "요청 실패, {delay}초 후 재시도 ({attempt}/{max})",
+        verification_failed: "Discord에 \"{name}\" 완료가 기록되지 않았습니다 (진행률 {progress}%)",
+        pause: "일시정지",
+        resume: "재개",
+        paused: "일시정지됨",
         up_next: "다음 퀘스트",
         submitted: "제출됨",
         pending: "대기 중",
@@ -105,6 +110,10 @@ export default {
         cache: "캐시",
         cache_desc: "애플리케이션은 문서 폴더를 사용하여 일부 캐시 파일(예: 시뮬레이션 게임)을 저장합니다.",
         open_cache_dir: "캐시 디렉토리 열기",
+        logs: "로그",
+        logs_desc: "최근 로그 메시지를 복사하여 버그 리포트에 첨부할 수 있습니다. 전체 로그는 앱의 로그 폴더에도 저장됩니다.",
+        log_level: "레벨",
+        copy_logs: "로그 복사",
         about: "정보",
         about_desc: "Discord 퀘스트를 자동으로 완료하는 도구입니다.",
         about_warning: "경고: 이 도구의 사용은 Discord 서비스 약관을 위반할 수 있습니다. 본인의 책임 하에 사용하세요.",
@@ -160,6 +169,26 @@ export default {
         start_all_play_desc: "{count}개의 플레이 퀘스트를 순서대로 실행하시겠습니까? 하나 완료 후 다음이 자동으로 시작됩니다.",
         cancel: "취소",
         accept: "수락",
-        start: "시작"
+        start: "시작",
+        resume_jobs_title: "중단된 퀘스트 재개",
+        resume_jobs_desc: "앱이 종료될 때 {count}개의 퀘스트가 진행 중이었습니다. 가장 최근 퀘스트를 저장된 진행도부터 재개하시겠습니까?",
+        discard: "삭제",
+        resume: "재개"
+    },
+    vault: {
+        title: "자격 증명 보관소",
+        desc: "직접 입력한 토큰을 암호 문구로 암호화하여 이 컴퓨터에 저장하므로 실행할 때마다 다시 붙여넣을 필요가 없습니다.",
+        passphrase: "암호 문구",
+        save: "현재 계정 저장",
+        unlock: "보관소 잠금 해제",
+        unlock_prompt: "저장된 계정 잠금 해제",
+        lock: "잠금",
+        forget: "보관소 삭제",
+        status_none: "저장된 계정 없음",
+        status_locked: "잠김",
+        status_unlocked: "잠금 해제됨",
+        auto_lock: "미사용 시 자동 잠금 (분)",
+        forget_title: "보관소를 삭제할까요?",
+        forget_desc: "저장된 모든 계정이 이 컴퓨터에서 삭제됩니다. 이 작업은 되돌릴 수 없습니다."
     }
 }
@@ -53,6 +53,11 @@ export default {
         discord_rewards: "Награды Discord",
         active_progress: "Прогресс текущего квеста",
         no_active: "Нет активного квеста",
+        retrying: "Запрос не удался, повтор через {delay} с ({attempt}/{max})",
+        verification_failed: "Discord не зафиксировал выполнение «{name}» (прогресс {progress}%)",
+        pause: "Пауза",
+        resume: "Продолжить",
+        paused: "На паузе",
         up_next: "Далее",
         submitted: "Отправлено",
         pending: "Ожидание",
@@ -106,6 +111,10 @@ export default {
         cache: "Кэш",
         cache_desc: "Приложение использует папку «Документы» для хранения некоторых файлов кэша (например, имитируемых игр).",
         open_cache_dir: "Открыть папку кэша",
+        logs: "Журнал",
+        logs_desc: "Скопируйте последние сообщения журнала, чтобы приложить их к отчёту об ошибке. Полный журнал также сохраняется в папке журналов приложения.",
+        log_level: "Уровень",
+        copy_logs: "Копировать журнал",
         about_desc: "Инструмент для автоматического завершения квестов Discord.",
         about_warning: "Предупреждение: Использование этого инструмента может нарушать Условия использования Discord. Используйте на свой страх и риск.",
         feedback: "Сообщить о проблеме",
@@ -160,6 +169,26 @@ export default {
         start_all_play_desc: "Запустить {count} Play квестов по очереди? Следующий начнётся после завершения предыдущего.",
         cancel: "Отмена",
         accept: "Принять",
-        start: "Начать"
+        start: "Начать",
+        resume_jobs_title: "Продолжить прерванные квесты",
+        resume_jobs_desc: "При закрытии приложения выполнялось квестов: {count}. Продолжить последний с сохранённого прогресса?",
+        discard: "Сбросить",
+        resume: "Продолжить"
+    },
+    vault: {
+        title: "Хранилище учётных данных",
+        desc: "Храните введённые вручную токены на этом компьютере в зашифрованном парольной фразой виде, чтобы не вставлять их при каждом запуске.",
+        passphrase: "Парольная фраза",
+        save: "Сохранить текущий аккаунт",
+        unlock: "Разблокировать хранилище",
+        unlock_prompt: "Разблокируйте сохранённые аккаунты",
+        lock: "Заблокировать",
+        forget: "Удалить хранилище",
+        status_none: "Нет сохранённых аккаунтов",
+        status_locked: "Заблокировано",
+        status_unlocked: "Разблокировано",
+        auto_lock: "Блокировать после бездействия (минуты)",
+        forget_title: "Удалить хранилище?",
+        forget_desc: "Все сохранённые аккаунты будут удалены с этого компьютера. Это действие нельзя отменить."
     }
 }
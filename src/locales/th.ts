@@ -53,6 +53,11 @@ export default {
         discord_rewards: "รางวัล Discord",
         active_progress: "ความคืบหน้าเควสต์ที่กำลังทำ",
         no_active: "ไม่มีเควสต์ที่กำลังทำ",
+        retrying: "คำขอล้มเหลว จะลองใหม่ใน {delay} วินาที ({attempt}/{max})",
+        verification_failed: "Discord ยังไม่ได้บันทึกว่า \"{name}\" เสร็จสิ้น (ความคืบหน้า {progress}%)",
+        pause: "หยุดชั่วคราว",
+        resume: "ทำต่อ",
+        paused: "หยุดชั่วคราวอยู่",
         up_next: "ถัดไป",
         submitted: "ส่งแล้ว",
         pending: "รอส่ง (ท้องถิ่น)",
@@ -105,6 +110,10 @@ export default {
         cache: "แคช",
         cache_desc: "แอปใช้โฟลเดอร์ Documents เก็บไฟล์แคช (เช่น เกมจำลอง)",
         open_cache_dir: "เปิดโฟลเดอร์แคช",
+        logs: "บันทึก",
+        logs_desc: "คัดลอกข้อความบันทึกล่าสุดเพื่อแนบกับรายงานข้อผิดพลาด บันทึกฉบับเต็มจะถูกเก็บไว้ในโฟลเดอร์บันทึกของแอปด้วย",
+        log_level: "ระดับ",
+        copy_logs: "คัดลอกบันทึก",
         about: "เกี่ยวกับ",
         about_desc: "เครื่องมือช่วยทำเควสต์ Discord อัตโนมัติ",
         about_warning: "คำเตือน: การใช้เครื่องมือนี้อาจขัดข้อกำหนดการใช้บริการของ Discord ใช้ความเสี่ยงของคุณเอง",
@@ -160,6 +169,26 @@ export default {
         start_all_play_desc: "รัน {count} เควสต์เล่นทีละอัน? แต่ละเควสต์จะเริ่มหลังอันก่อนเสร็จ",
         cancel: "ยกเลิก",
         accept: "ยอมรับ",
-        start: "เริ่ม"
+        start: "เริ่ม",
+        resume_jobs_title: "ทำเควสต์ที่ถูกขัดจังหวะต่อ",
+        resume_jobs_desc: "มี {count} เควสต์ที่ยังทำงานอยู่ตอนปิดแอป ต้องการทำเควสต์ล่าสุดต่อจากความคืบหน้าที่บันทึกไว้หรือไม่?",
+        discard: "ทิ้ง",
+        resume: "ทำต่อ"
+    },
+    vault: {
+        title: "ห้องนิรภัยข้อมูลรับรอง",
+        desc: "เก็บโทเค็นที่ป้อนเองไว้ในคอมพิวเตอร์เครื่องนี้โดยเข้ารหัสด้วยวลีรหัสผ่าน เพื่อไม่ต้องวางใหม่ทุกครั้งที่เปิดแอป",
+        passphrase: "วลีรหัสผ่าน",
+        save: "บันทึกบัญชีปัจจุบัน",
+        unlock: "ปลดล็อกห้องนิรภัย",
+        unlock_prompt: "ปลดล็อกบัญชีที่บันทึกไว้",
+        lock: "ล็อก",
+        forget: "ลบห้องนิรภัย",
+        status_none: "ไม่มีบัญชีที่บันทึกไว้",
+        status_locked: "ล็อกอยู่",
+        status_unlocked: "ปลดล็อกแล้ว",
+        auto_lock: "ล็อกเมื่อไม่ได้ใช้งาน (นาที)",
+        forget_title: "ลบห้องนิรภัยหรือไม่?",
+        forget_desc: "บัญชีที่บันทึกไว้ทั้งหมดจะถูกลบออกจากคอมพิวเตอร์เครื่องนี้ และไม่สามารถย้อนกลับได้"
     }
 }
@@ -53,6 +53,11 @@ export default {
         discord_rewards: "Discord 獎勵",
         active_progress: "當前任務進度",
         no_active: "暫無活躍任務",
+        retrying: "請求失敗，{delay} 秒後重試 ({attempt}/{max})",
+        verification_failed: "Discord 尚未記錄「{name}」已完成（進度 {progress}%）",
+        pause: "暫停",
+        resume: "繼續",
+        paused: "已暫停",
         up_next: "隊列中",
         submitted: "已提交",
         pending: "待提交",
@@ -106,6 +111,10 @@ export default {
         cache: "快取",
         cache_desc: "應用程式使用「文件」目錄存儲一些快取檔案（例如模擬的遊戲）。",
         open_cache_dir: "打開快取目錄",
+        logs: "日誌",
+        logs_desc: "複製最近的日誌訊息，以附加到錯誤回報中。完整日誌也會保存在應用程式的日誌目錄中。",
+        log_level: "等級",
+        copy_logs: "複製日誌",
         about_desc: "一個幫助自動完成 Discord 任務的工具。",
         about_warning: "警告：使用此工具可能違反 Discord 服務條款。請自行承擔風險。",
         feedback: "問題回報",
@@ -160,6 +169,26 @@ export default {
         start_all_play_desc: "依序執行 {count} 個遊玩任務？每完成一個後自動開始下一個。",
         cancel: "取消",
         accept: "接受",
-        start: "開始"
+        start: "開始",
+        resume_jobs_title: "繼續中斷的任務",
+        resume_jobs_desc: "應用關閉時有 {count} 個任務仍在執行。要從已儲存的進度繼續最近的任務嗎？",
+        discard: "捨棄",
+        resume: "繼續"
+    },
+    vault: {
+        title: "憑證保險庫",
+        desc: "將手動輸入的權杖以密碼短語加密後保存在這台電腦上，無需每次啟動時重新貼上。",
+        passphrase: "密碼短語",
+        save: "保存目前帳號",
+        unlock: "解鎖保險庫",
+        unlock_prompt: "解鎖已保存的帳號",
+        lock: "鎖定",
+        forget: "刪除保險庫",
+        status_none: "沒有已保存的帳號",
+        status_locked: "已鎖定",
+        status_unlocked: "已解鎖",
+        auto_lock: "閒置後鎖定（分鐘）",
+        forget_title: "要刪除保險庫嗎？",
+        forget_desc: "所有已保存的帳號都將從這台電腦上刪除。此操作無法復原。"
     }
 }
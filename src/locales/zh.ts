@@ -53,6 +53,11 @@ export default {
         discord_rewards: "Discord 奖励",
         active_progress: "当前任务进度",
         no_active: "暂无活跃任务",
+        retrying: "请求失败，{delay} 秒后重试 ({attempt}/{max})",
+        verification_failed: "Discord 尚未记录「{name}」已完成（进度 {progress}%）",
+        pause: "暂停",
+        resume: "继续",
+        paused: "已暂停",
         up_next: "队列中",
         submitted: "已提交",
         pending: "待提交",
@@ -105,6 +110,10 @@ export default {
         cache: "缓存",
         cache_desc: "应用程序使用“文档”目录存储一些缓存文件（例如模拟的游戏）。",
         open_cache_dir: "打开缓存目录",
+        logs: "日志",
+        logs_desc: "复制最近的日志消息，以附加到错误报告中。完整日志也会保存在应用程序的日志目录中。",
+        log_level: "级别",
+        copy_logs: "复制日志",
         about: "关于",
         about_desc: "一个帮助自动完成 Discord 任务的工具。",
         about_warning: "警告：使用此工具可能违反 Discord 的服务条款。请自行承担风险。",
@@ -160,6 +169,26 @@ export default {
         start_all_play_desc: "按顺序运行 {count} 个游玩任务？每完成一个后自动开始下一个。",
         cancel: "取消",
         accept: "接受",
-        start: "开始"
+        start: "开始",
+        resume_jobs_title: "继续中断的任务",
+        resume_jobs_desc: "应用关闭时有 {count} 个任务仍在运行。要从已保存的进度继续最近的任务吗？",
+        discard: "丢弃",
+        resume: "继续"
+    },
+    vault: {
+        title: "凭证保险库",
+        desc: "将手动输入的令牌用密码短语加密后保存在这台电脑上，无需每次启动时重新粘贴。",
+        passphrase: "密码短语",
+        save: "保存当前账号",
+        unlock: "解锁保险库",
+        unlock_prompt: "解锁已保存的账号",
+        lock: "锁定",
+        forget: "删除保险库",
+        status_none: "没有已保存的账号",
+        status_locked: "已锁定",
+        status_unlocked: "已解锁",
+        auto_lock: "闲置后锁定（分钟）",
+        forget_title: "要删除保险库吗？",
+        forget_desc: "所有已保存的账号都将从这台电脑上删除。此操作无法撤销。"
     }
 }
@@ -1,14 +1,25 @@
 import { defineStore } from 'pinia'
 import { ref } from 'vue'
-import type { DiscordUser, ExtractedAccount } from '@/api/tauri'
-import { autoDetectToken, setToken } from '@/api/tauri'
+import type { DiscordUser, ExtractedAccount, VaultStatus } from '@/api/tauri'
+import {
+  autoDetectToken,
+  selectAccount,
+  setToken,
+  getVaultStatus,
+  saveToVault as saveAccountToVault,
+  unlockVault as unlockSavedAccounts,
+  lockVault as lockSavedAccounts,
+  forgetVault as forgetSavedAccounts,
+  setVaultAutoLock
+} from '@/api/tauri'
 
 export const useAuthStore = defineStore('auth', () => {
   const user = ref<DiscordUser | null>(null)
-  const token = ref<string | null>(null)
   const loading = ref(false)
   const error = ref<string | null>(null)
   const detectedAccounts = ref<ExtractedAccount[]>([])
+  // null when the backend has no app data dir to keep a vault in
+  const vaultStatus = ref<VaultStatus | null>(null)
 
   async function tryAutoDetect() {
     loading.value = true
@@ -23,7 +34,7 @@ export const useAuthStore = defineStore('auth', () => {
       if (accounts.length === 1) {
         console.log('Single account found, logging in...')
         // Only one account found, login automatically
-        await loginWithToken(accounts[0].token)
+        await loginWithAccount(accounts[0].handle)
       } else {
         console.log('Multiple accounts found, updating detectedAccounts state...')
         // Multiple accounts, let UI handle selection
@@ -32,42 +43,124 @@ export const useAuthStore = defineStore('auth', () => {
       return true
     } catch (e) {
       console.error('Auto detect failed:', e)
-      error.value = e as string
+      error.value = String(e)
       return false
     } finally {
       loading.value = false
     }
   }
 
+  // Manually entered token; detected accounts go through loginWithAccount
   async function loginWithToken(tokenValue: string) {
+    return await login(() => setToken(tokenValue))
+  }
+
+  // The backend keeps the token; only the account handle is known here
+  async function loginWithAccount(handle: string) {
+    return await login(() => selectAccount(handle))
+  }
+
+  async function login(request: () => Promise<DiscordUser>) {
     loading.value = true
     error.value = null
     try {
-      user.value = await setToken(tokenValue)
-      token.value = tokenValue
+      user.value = await request()
       return true
     } catch (e) {
-      error.value = e as string
+      error.value = String(e)
       return false
     } finally {
       loading.value = false
     }
   }
 
+  // The vault may have locked itself since the last look
+  async function refreshVaultStatus() {
+    try {
+      vaultStatus.value = await getVaultStatus()
+    } catch (e) {
+      console.error('Failed to get vault status:', e)
+      vaultStatus.value = null
+    }
+  }
+
+  // Same flow as tryAutoDetect, with the accounts saved in the vault
+  async function unlockVault(passphrase: string) {
+    loading.value = true
+    error.value = null
+    detectedAccounts.value = []
+
+    try {
+      const accounts = await unlockSavedAccounts(passphrase)
+      await refreshVaultStatus()
+      if (accounts.length === 1) {
+        await loginWithAccount(accounts[0].handle)
+      } else {
+        detectedAccounts.value = accounts
+      }
+      return true
+    } catch (e) {
+      error.value = String(e)
+      return false
+    } finally {
+      loading.value = false
+    }
+  }
+
+  async function saveToVault(passphrase?: string) {
+    error.value = null
+    try {
+      await saveAccountToVault(passphrase || undefined)
+      return true
+    } catch (e) {
+      error.value = String(e)
+      return false
+    } finally {
+      await refreshVaultStatus()
+    }
+  }
+
+  async function lockVault() {
+    await lockSavedAccounts()
+    await refreshVaultStatus()
+  }
+
+  async function forgetVault() {
+    await forgetSavedAccounts()
+    await refreshVaultStatus()
+  }
+
+  async function setAutoLockMinutes(minutes: number) {
+    await setVaultAutoLock(Math.max(1, Math.round(minutes)) * 60)
+    await refreshVaultStatus()
+  }
+
+  // The backend is still logged in after a webview reload
+  function restoreUser(backendUser: DiscordUser) {
+    user.value = backendUser
+  }
+
   function logout() {
     user.value = null
-    token.value = null
     error.value = null
   }
 
   return {
     user,
-    token,
     loading,
     error,
     detectedAccounts,
+    vaultStatus,
     tryAutoDetect,
     loginWithToken,
+    loginWithAccount,
+    restoreUser,
+    refreshVaultStatus,
+    unlockVault,
+    saveToVault,
+    lockVault,
+    forgetVault,
+    setAutoLockMinutes,
     logout
   }
 })
@@ -1,14 +1,23 @@
 import { defineStore } from 'pinia'
 import { ref, watch } from 'vue'
-import type { Quest } from '@/api/tauri'
+import type { BackendStatus, Quest, QuestEvent, QuestJob, QuestRetryNotice, QuestVerificationReport } from '@/api/tauri'
 import {
   getQuests,
+  runQuest as runQuestApi,
   startVideoQuest,
   startStreamQuest,
   stopQuest,
   onQuestProgress,
   onQuestComplete,
   onQuestError,
+  onQuestRetrying,
+  onQuestPaused,
+  onQuestResumed,
+  onQuestSessionClosed,
+  onQuestVerified,
+  onQuestVerificationFailed,
+  pauseQuest,
+  resumeQuest,
   createSimulatedGame,
   runSimulatedGame,
   stopSimulatedGame,
@@ -16,7 +25,7 @@ import {
   connectToDiscordRpc,
   acceptQuest,
   startGameHeartbeatQuest,
-  forceVideoProgress
+  resumeJob as resumeJobApi
 } from '@/api/tauri'
 import { homeDir, sep } from '@tauri-apps/api/path'
 import { emit } from '@tauri-apps/api/event'
@@ -35,6 +44,11 @@ export const useQuestsStore = defineStore('quests', () => {
   const activeQuestType = ref<'video' | 'stream' | 'game' | null>(null)
   const activeQuestProgress = ref(0)
   const activeQuestTargetDuration = ref(0)
+  // Set while the backend is backing off after a transient failure
+  const retryNotice = ref<QuestRetryNotice | null>(null)
+  const activeQuestPaused = ref(false)
+  // Last completion that Discord didn't confirm, until dismissed
+  const verificationFailure = ref<QuestVerificationReport | null>(null)
 
   // Local Progress Simulation State
   const localProgress = ref(0)
@@ -82,6 +96,10 @@ export const useQuestsStore = defineStore('quests', () => {
   let progressUnlisten: (() => void) | null = null
   let completeUnlisten: (() => void) | null = null
   let errorUnlisten: (() => void) | null = null
+  let retryingUnlisten: (() => void) | null = null
+  let pausedUnlisten: (() => void) | null = null
+  let resumedUnlisten: (() => void) | null = null
+  let sessionClosedUnlisten: (() => void) | null = null
   let pollingTimer: ReturnType<typeof setInterval> | null = null
 
   // Simulation internal vars
@@ -95,12 +113,28 @@ export const useQuestsStore = defineStore('quests', () => {
     try {
       quests.value = await getQuests()
     } catch (e) {
-      error.value = e as string
+      error.value = String(e)
     } finally {
       if (!silent) loading.value = false
     }
   }
 
+  // Verification results arrive after quest-complete, once the per-quest
+  // listeners are gone, so these stay registered for the store's lifetime
+  onQuestVerified(({ detail: report }) => {
+    if (verificationFailure.value?.quest_id === report.quest_id) {
+      verificationFailure.value = null
+    }
+  })
+  onQuestVerificationFailed(({ detail: report }) => {
+    verificationFailure.value = report
+    fetchQuests(true)
+  })
+
+  function dismissVerificationFailure() {
+    verificationFailure.value = null
+  }
+
   function checkActiveQuestStatus() {
     if (!activeQuestId.value) return
     const quest = quests.value.find(q => q.id === activeQuestId.value)
@@ -213,8 +247,42 @@ export const useQuestsStore = defineStore('quests', () => {
     }
   }
 
+  // The backend can run quests side by side, but this store tracks a single
+  // active quest, so stop the current one before starting another
+  async function releaseActiveQuest() {
+    if (activeQuestId.value) {
+      try {
+        await stopQuest(activeQuestId.value)
+      } catch (e) {
+        console.error('Failed to stop previous quest:', e)
+      }
+    }
+  }
+
+  // Let the backend decide how to complete the quest; returns false if it was already completed
+  async function runQuest(questId: string): Promise<boolean> {
+    try {
+      await releaseActiveQuest()
+      const job = await runQuestApi(questId, speedMultiplier.value, heartbeatInterval.value)
+      if (!job) return false
+
+      activeQuestId.value = job.quest_id
+      activeQuestType.value = job.kind
+      activeQuestProgress.value = job.progress
+      activeQuestTargetDuration.value = job.seconds_needed
+
+      startProgressSimulation(job.kind === 'video' ? job.speed_multiplier : 1.0)
+      setupListeners()
+      return true
+    } catch (e) {
+      error.value = String(e)
+      throw e
+    }
+  }
+
   async function startVideo(questId: string, secondsNeeded: number, initialProgress: number) {
     try {
+      await releaseActiveQuest()
       const progressPct = (secondsNeeded > 0) ? (initialProgress / secondsNeeded) * 100 : 0
       await startVideoQuest(questId, secondsNeeded, progressPct, speedMultiplier.value, heartbeatInterval.value)
       activeQuestId.value = questId
@@ -225,13 +293,14 @@ export const useQuestsStore = defineStore('quests', () => {
       startProgressSimulation(speedMultiplier.value)
       setupListeners()
     } catch (e) {
-      error.value = e as string
+      error.value = String(e)
       throw e
     }
   }
 
   async function startStream(questId: string, streamKey: string, secondsNeeded: number, initialProgress: number) {
     try {
+      await releaseActiveQuest()
       const progressPct = (secondsNeeded > 0) ? (initialProgress / secondsNeeded) * 100 : 0
       await startStreamQuest(questId, streamKey, secondsNeeded, progressPct)
       activeQuestId.value = questId
@@ -242,11 +311,79 @@ export const useQuestsStore = defineStore('quests', () => {
       startProgressSimulation(1.0)
       setupListeners()
     } catch (e) {
-      error.value = e as string
+      error.value = String(e)
       throw e
     }
   }
 
+  // Restart a quest the backend saved before the app was closed
+  async function resumeJob(job: QuestJob) {
+    try {
+      await releaseActiveQuest()
+      await resumeJobApi(job.quest_id)
+      activeQuestId.value = job.quest_id
+      activeQuestType.value = job.kind
+      activeQuestProgress.value = job.progress
+      activeQuestTargetDuration.value = job.seconds_needed
+
+      startProgressSimulation(job.kind === 'video' ? job.speed_multiplier : 1.0)
+      setupListeners()
+    } catch (e) {
+      error.value = String(e)
+      throw e
+    }
+  }
+
+  // Pick up a quest the backend is still running after a webview reload
+  async function reattach(status: BackendStatus) {
+    if (activeQuestId.value) return
+
+    const running = status.running_quests[0]
+    if (running) {
+      console.log('Reattaching to running quest:', running)
+      activeQuestId.value = running.quest_id
+      activeQuestType.value = running.kind
+      activeQuestProgress.value = running.progress
+      activeQuestTargetDuration.value = running.seconds_needed
+      setupListeners()
+      activeQuestPaused.value = running.paused
+      if (!running.paused) {
+        startProgressSimulation(running.kind === 'video' ? speedMultiplier.value : 1.0)
+      }
+      return
+    }
+
+    // Simulated games have no backend task; find their quest and poll it again
+    const game = status.simulated_games[0]
+    if (!game) return
+    if (quests.value.length === 0) await fetchQuests(true)
+    const quest = quests.value.find(q => q.config.application?.id === game.app_id && !q.user_status?.completed_at)
+    activeGameExe.value = game.executable_name
+    if (!quest) return
+
+    console.log('Reattaching to simulated game:', game)
+    let seconds = 0
+    const taskConfig = quest.config.task_config || quest.config.task_config_v2
+    if (taskConfig?.tasks) {
+      const tasks = Object.values(taskConfig.tasks)
+      if (tasks.length > 0) seconds = tasks[0].target || 0
+    }
+
+    let done = 0
+    if (quest.user_status?.progress) {
+      const vals = Object.values(quest.user_status.progress)
+      if (vals.length > 0) done = vals[0].value || 0
+    }
+
+    activeQuestId.value = quest.id
+    activeQuestType.value = 'game'
+    activeQuestTargetDuration.value = seconds
+    activeQuestProgress.value = seconds > 0 ? (done / seconds) * 100 : 0
+    startProgressSimulation(1.0)
+    setupListeners()
+    startPolling()
+  }
+
   async function startPlay(quest: Quest, secondsNeeded: number, initialProgress: number) {
     loading.value = true
     error.value = null
@@ -261,6 +398,7 @@ export const useQuestsStore = defineStore('quests', () => {
         console.log(`Starting game quest via direct heartbeat for AppID: ${appId}`)
 
         const progressPct = (secondsNeeded > 0) ? (initialProgress / secondsNeeded) * 100 : 0
+        await releaseActiveQuest()
         await startGameHeartbeatQuest(
           quest.id,
           appId,
@@ -327,7 +465,7 @@ export const useQuestsStore = defineStore('quests', () => {
         startPolling()
       }
     } catch (e) {
-      error.value = e as string
+      error.value = String(e)
       // Clean up if started (only for simulate mode)
       if (activeGameExe.value) {
         try {
@@ -341,6 +479,20 @@ export const useQuestsStore = defineStore('quests', () => {
     }
   }
 
+  // Pause / resume the backend task driving the active quest (not the simulated game)
+  async function togglePause() {
+    if (!activeQuestId.value) return
+    try {
+      if (activeQuestPaused.value) {
+        await resumeQuest(activeQuestId.value)
+      } else {
+        await pauseQuest(activeQuestId.value)
+      }
+    } catch (e) {
+      error.value = String(e)
+    }
+  }
+
   async function stop(reason?: 'user' | 'auto') {
     stopping.value = true
     const wasActiveQuestId = activeQuestId.value
@@ -349,19 +501,7 @@ export const useQuestsStore = defineStore('quests', () => {
     stopProgressSimulation()
 
     try {
-      // Force Save Logic for Video Quests
-      if (activeQuestId.value && activeQuestType.value === 'video' && activeQuestTargetDuration.value > 0) {
-        try {
-          const currentSeconds = (localProgress.value / 100) * activeQuestTargetDuration.value
-          // Only force if we have significant progress
-          if (currentSeconds > 0) {
-            console.log(`Force submitting video progress: ${currentSeconds.toFixed(1)}s (ID: ${activeQuestId.value})`)
-            await forceVideoProgress(activeQuestId.value, currentSeconds)
-          }
-        } catch (e) {
-          console.error('Failed to force submit progress on stop:', e)
-        }
-      }
+      // Final video progress is saved by the backend when the quest is stopped
 
       // If manually stopping (user), clear both queues. If auto (completed), play queue will advance below.
       if (reason !== 'auto') {
@@ -412,10 +552,12 @@ export const useQuestsStore = defineStore('quests', () => {
         activeGameExe.value = null
       }
 
-      try {
-        await stopQuest()
-      } catch (e) {
-        // Ignore error if no quest running
+      if (wasActiveQuestId) {
+        try {
+          await stopQuest(wasActiveQuestId)
+        } catch (e) {
+          // Ignore error if no quest running
+        }
       }
 
       activeQuestId.value = null
@@ -441,14 +583,21 @@ export const useQuestsStore = defineStore('quests', () => {
     }
   }
 
+  // Events from other quests (e.g. one started from another view) are ignored
+  function isForActiveQuest(event: QuestEvent<unknown>) {
+    return !activeQuestId.value || event.quest_id === activeQuestId.value
+  }
+
   function setupListeners() {
     cleanupListeners()
 
     console.log('Setting up quest progress listeners...')
 
-    onQuestProgress((progress) => {
-      console.log('Received quest-progress event:', progress)
-      activeQuestProgress.value = progress
+    onQuestProgress((event) => {
+      if (!isForActiveQuest(event)) return
+      console.log('Received quest-progress event:', event)
+      activeQuestProgress.value = event.progress
+      retryNotice.value = null
       // For Play quests, update local state or log since no direct feedback loop? 
       // Discord RPC is one-way, but we might listen to Discord Gateway for activity updates if needed.
       // But user_status updates come from backend polling or events.
@@ -457,7 +606,8 @@ export const useQuestsStore = defineStore('quests', () => {
       console.log('Quest progress listener ready')
     })
 
-    onQuestComplete(() => {
+    onQuestComplete((event) => {
+      if (!isForActiveQuest(event)) return
       console.log('Received quest-complete event')
 
       // Play queue: advance to next
@@ -512,9 +662,10 @@ export const useQuestsStore = defineStore('quests', () => {
       console.log('Quest complete listener ready')
     })
 
-    onQuestError((err) => {
-      console.log('Received quest-error event:', err)
-      error.value = err
+    onQuestError((event) => {
+      if (!isForActiveQuest(event)) return
+      console.log('Received quest-error event:', event.detail.message)
+      error.value = event.detail.message
       activeQuestId.value = null
       activeQuestType.value = null
       activeQuestProgress.value = 0
@@ -528,6 +679,43 @@ export const useQuestsStore = defineStore('quests', () => {
       errorUnlisten = unlisten
       console.log('Quest error listener ready')
     })
+
+    onQuestRetrying((event) => {
+      if (!isForActiveQuest(event)) return
+      console.log('Received quest-retrying event:', event.detail)
+      retryNotice.value = event.detail
+    }).then((unlisten) => {
+      retryingUnlisten = unlisten
+      console.log('Quest retrying listener ready')
+    })
+
+    onQuestPaused((event) => {
+      if (!isForActiveQuest(event)) return
+      console.log('Received quest-paused event')
+      activeQuestPaused.value = true
+      stopProgressSimulation()
+    }).then((unlisten) => {
+      pausedUnlisten = unlisten
+    })
+
+    onQuestResumed((event) => {
+      if (!isForActiveQuest(event)) return
+      console.log('Received quest-resumed event')
+      activeQuestPaused.value = false
+      startProgressSimulation(simCurrentSpeed)
+    }).then((unlisten) => {
+      resumedUnlisten = unlisten
+    })
+
+    onQuestSessionClosed(({ detail }) => {
+      if (detail.acknowledged) {
+        console.log('Game session closed')
+      } else {
+        console.warn('Discord did not acknowledge the end of the game session')
+      }
+    }).then((unlisten) => {
+      sessionClosedUnlisten = unlisten
+    })
   }
 
   function cleanupListeners() {
@@ -544,6 +732,24 @@ export const useQuestsStore = defineStore('quests', () => {
       errorUnlisten()
       errorUnlisten = null
     }
+    if (retryingUnlisten) {
+      retryingUnlisten()
+      retryingUnlisten = null
+    }
+    if (pausedUnlisten) {
+      pausedUnlisten()
+      pausedUnlisten = null
+    }
+    if (resumedUnlisten) {
+      resumedUnlisten()
+      resumedUnlisten = null
+    }
+    if (sessionClosedUnlisten) {
+      sessionClosedUnlisten()
+      sessionClosedUnlisten = null
+    }
+    retryNotice.value = null
+    activeQuestPaused.value = false
   }
 
   function setSpeedMultiplier(speed: number) {
@@ -556,7 +762,7 @@ export const useQuestsStore = defineStore('quests', () => {
       // Optimistic update
       updateQuestEnrollment(questId, new Date().toISOString())
     } catch (e) {
-      error.value = e as string
+      error.value = String(e)
       throw e
     }
   }
@@ -647,31 +853,16 @@ export const useQuestsStore = defineStore('quests', () => {
 
     try {
       console.log(`Queue processing: ${quest.id}`)
-      // Start video
-      // Calculate duration needed
-      let seconds = 0
-      const taskConfig = quest.config.task_config || quest.config.task_config_v2
-      if (taskConfig?.tasks) {
-        const tasks = Object.values(taskConfig.tasks)
-        if (tasks.length > 0) seconds = tasks[0].target || 0
-      }
+      // The backend refetches the quest and picks its target and progress
+      const started = await runQuest(quest.id)
 
-      // Check if already partial
-      let progress = 0
-      if (quest.user_status?.progress) {
-        const vals = Object.values(quest.user_status.progress)
-        if (vals.length > 0) progress = vals[0].value || 0
-      }
-
-      // If completed, skip
-      if (quest.user_status?.completed_at) {
+      // Already completed, skip
+      if (!started) {
         questQueue.value.shift()
         processQueue()
         return
       }
 
-      await startVideo(quest.id, seconds, progress)
-
       // Now we wait for completion event. 
       // setupListeners handles `onQuestComplete`.
       // We need to hook into that.
@@ -695,6 +886,9 @@ export const useQuestsStore = defineStore('quests', () => {
     activeQuestType,
     activeQuestProgress,
     activeQuestTargetDuration,
+    retryNotice,
+    activeQuestPaused,
+    verificationFailure,
     localProgress, // Export local progress
     speedMultiplier,
     heartbeatInterval,
@@ -708,10 +902,15 @@ export const useQuestsStore = defineStore('quests', () => {
     isPlayQueueRunning,
     fetchQuests,
     updateQuestEnrollment,
+    runQuest,
     startVideo,
     startStream,
     startPlay,
+    resumeJob,
+    reattach,
+    togglePause,
     stop,
+    dismissVerificationFailure,
     setSpeedMultiplier,
     acceptQuest: acceptQuestWrapper,
     acceptAllQuests,